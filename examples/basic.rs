@@ -8,34 +8,13 @@ fn run_app() {
 
     // Pure component
 
-    pub struct MyComponentBuilder<T> {
-        value: Option<T>,
-    }
-
-    impl<T: PartialEq + Clone + 'static> MyComponentBuilder<T> {
-        pub fn set_attr_value(&mut self, value: T) -> &mut Self {
-            self.value.replace(value.into());
-            self
-        }
-        pub fn finish(&mut self) -> VNode {
-            VNode::from(VNodePureComponent::new(MyComponent {
-                value: self.value.take().expect("missing property value"),
-            }))
-        }
-    }
-
-    #[derive(PartialEq, Clone)]
-    pub struct MyComponent<T = ()> {
+    #[derive(PartialEq, Clone, yo_html::HtmlBuilder)]
+    #[html(finish = VNode, with = VNodePureComponent::new, typestate)]
+    pub struct MyComponent<T: PartialEq + Clone + 'static = ()> {
         value: T,
     }
 
-    impl<T> MyComponent<T> {
-        pub fn builder(_tag: &'static str) -> MyComponentBuilder<T> {
-            MyComponentBuilder { value: None }
-        }
-    }
-
-    impl<T> PureComponent for MyComponent<T> {
+    impl<T: PartialEq + Clone + 'static> PureComponent for MyComponent<T> {
         fn render(&self) -> VNode {
             html! {
                 <p>{"My component"}</p>
@@ -45,43 +24,13 @@ fn run_app() {
 
     // Stateful component
 
-    pub struct CounterBuilder {
-        min: Option<i32>,
-        max: Option<i32>,
-    }
-
-    impl CounterBuilder {
-        pub fn set_attr_min(&mut self, min: i32) -> &mut Self {
-            self.min.replace(min);
-            self
-        }
-        pub fn set_attr_max(&mut self, max: i32) -> &mut Self {
-            self.max.replace(max);
-            self
-        }
-        pub fn finish(&mut self) -> VNode {
-            VNode::from(VNodeStatefulComponent::new(Counter {
-                min: self.min,
-                max: self.max,
-            }))
-        }
-    }
-
-    #[derive(PartialEq, Clone)]
+    #[derive(PartialEq, Clone, yo_html::HtmlBuilder)]
+    #[html(finish = VNode, with = VNodeStatefulComponent::new, typestate)]
     pub struct Counter {
         min: Option<i32>,
         max: Option<i32>,
     }
 
-    impl Counter {
-        pub fn builder(_tag: &'static str) -> CounterBuilder {
-            CounterBuilder {
-                min: None,
-                max: None,
-            }
-        }
-    }
-
     impl StatefulComponent for Counter {
         fn update(&mut self, other: Self) -> bool {
             let should_update = *self != other;
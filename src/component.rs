@@ -0,0 +1,143 @@
+use crate::derive::{generate_builder, BuilderOptions};
+use syn::spanned::Spanned;
+
+/// The options of the attribute: `#[component(wrap = path)]`.
+#[derive(Default)]
+pub(crate) struct ComponentOptions {
+    wrap: Option<syn::Expr>,
+}
+
+impl ComponentOptions {
+    pub(crate) fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("wrap") {
+            self.wrap = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unknown option, expected `wrap`"))
+        }
+    }
+}
+
+/// Generate a props struct with the name of the function and a typestate builder for it. The
+/// function itself is kept: it lives in the value namespace while the props live in the type
+/// namespace.
+pub(crate) fn component(
+    options: ComponentOptions,
+    mut item: syn::ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let vis = &item.vis;
+    let name = &item.sig.ident;
+    let generics = &item.sig.generics;
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    let syn::ReturnType::Type(_, output) = &item.sig.output else {
+        return Err(syn::Error::new_spanned(
+            &item.sig,
+            "a component must return what it renders",
+        ));
+    };
+
+    // NOTE: the derives are for the props, the documentation is for both.
+    let (derives, attrs): (Vec<_>, Vec<_>) = std::mem::take(&mut item.attrs)
+        .into_iter()
+        .partition(|x| x.path().is_ident("derive"));
+    item.attrs = attrs;
+    let docs = item.attrs.iter().filter(|x| x.path().is_ident("doc"));
+
+    let mut fields = Vec::new();
+    let mut props_fields = Vec::new();
+    let mut args = Vec::new();
+    let mut cloned_args = Vec::new();
+    for input in item.sig.inputs.iter_mut() {
+        let syn::FnArg::Typed(pat_type) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "a component can't have a receiver",
+            ));
+        };
+        let syn::Pat::Ident(syn::PatIdent { ident, .. }) = pat_type.pat.as_ref() else {
+            return Err(syn::Error::new_spanned(
+                &pat_type.pat,
+                "the arguments of a component must be identifiers",
+            ));
+        };
+        let ty = &pat_type.ty;
+        let (html_attrs, attrs): (Vec<_>, Vec<_>) = std::mem::take(&mut pat_type.attrs)
+            .into_iter()
+            .partition(|x| x.path().is_ident("html"));
+        pat_type.attrs = attrs;
+
+        // NOTE: the children are collected in a `Vec` that is passed as it is or collected into
+        //       the type of the argument.
+        let is_vec = matches!(
+            ty.as_ref(),
+            syn::Type::Path(syn::TypePath { qself: None, path })
+                if path.segments.last().is_some_and(|x| x.ident == "Vec")
+        );
+        let field_ty = if ident == "children" && !is_vec {
+            quote::quote! { ::std::vec::Vec<#ty> }
+        } else {
+            quote::quote! { #ty }
+        };
+        fields.push(quote::quote! {
+            #(#html_attrs)*
+            #vis #ident: #field_ty
+        });
+        props_fields.push(quote::quote! { #vis #ident: #field_ty });
+        if ident == "children" && !is_vec {
+            args.push(quote::quote_spanned! {ty.span()=>
+                ::core::iter::FromIterator::from_iter(props.#ident)
+            });
+            cloned_args.push(quote::quote_spanned! {ty.span()=>
+                ::core::iter::FromIterator::from_iter(props.#ident.iter().cloned())
+            });
+        } else {
+            args.push(quote::quote! { props.#ident });
+            cloned_args.push(quote::quote_spanned! {ty.span()=>
+                ::core::clone::Clone::clone(&props.#ident)
+            });
+        }
+    }
+
+    let props: syn::DeriveInput = syn::parse_quote! {
+        #(#derives)*
+        #(#docs)*
+        #vis struct #name #generics #where_clause {
+            #(#fields,)*
+        }
+    };
+
+    // NOTE: a wrapped component gets a function that renders it from a reference.
+    let with = match &options.wrap {
+        Some(wrap) => syn::parse_quote! {
+            |props: #name #ty_generics| (#wrap)(props, |props: &#name #ty_generics| {
+                #name(#(#cloned_args),*)
+            })
+        },
+        None => syn::parse_quote! {
+            |props: #name #ty_generics| #name(#(#args),*)
+        },
+    };
+    let builder = generate_builder(
+        &props,
+        &BuilderOptions {
+            finish: Some(output.as_ref().clone()),
+            with: Some(with),
+            typestate: true,
+            owned: false,
+        },
+    )?;
+
+    let props_attrs = &props.attrs;
+
+    Ok(quote::quote! {
+        #(#props_attrs)*
+        #vis struct #name #generics #where_clause {
+            #(#props_fields,)*
+        }
+
+        #builder
+
+        #[allow(non_snake_case)]
+        #item
+    })
+}
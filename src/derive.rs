@@ -0,0 +1,525 @@
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+/// The options of the struct: `#[html(finish = Type, with = expr, typestate, owned)]`.
+#[derive(Default)]
+pub(crate) struct BuilderOptions {
+    pub(crate) finish: Option<syn::Type>,
+    pub(crate) with: Option<syn::Expr>,
+    /// The required fields are tracked in the type of the builder so a missing one is a compile
+    /// error instead of a panic.
+    pub(crate) typestate: bool,
+    /// All the methods take the builder by value, like with `typestate`, instead of borrowing it.
+    pub(crate) owned: bool,
+}
+
+/// The options of a field: `#[html(default)]`, `#[html(default = expr)]` and `#[html(into)]`.
+#[derive(Default)]
+struct FieldOptions {
+    default: Option<Option<syn::Expr>>,
+    into: bool,
+}
+
+/// How a field is stored in the builder and passed to the props.
+enum FieldKind {
+    /// `children: Vec<T>` is filled by `add_child`.
+    Children(syn::Type),
+    /// `Vec<T>` is filled by `set_attr_*` and `add_attr_*`.
+    Vec(syn::Type),
+    /// `Option<T>` is not required.
+    Option(syn::Type),
+    /// Any other type is required unless it has a default.
+    Required,
+}
+
+struct Field<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a syn::Type,
+    kind: FieldKind,
+    options: FieldOptions,
+}
+
+impl Field<'_> {
+    fn is_required(&self) -> bool {
+        matches!(self.kind, FieldKind::Required) && self.options.default.is_none()
+    }
+}
+
+pub(crate) fn derive_html_builder(
+    input: syn::DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut options = BuilderOptions::default();
+    for attr in input.attrs.iter().filter(|x| x.path().is_ident("html")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("finish") {
+                options.finish = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("with") {
+                options.with = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("typestate") {
+                options.typestate = true;
+            } else if meta.path.is_ident("owned") {
+                options.owned = true;
+            } else {
+                return Err(
+                    meta.error("unknown option, expected `finish`, `with`, `typestate` or `owned`")
+                );
+            }
+            Ok(())
+        })?;
+    }
+    if let (None, Some(with)) = (&options.finish, &options.with) {
+        return Err(syn::Error::new_spanned(
+            with,
+            "`with` requires the type returned by `finish`",
+        ));
+    }
+
+    generate_builder(&input, &options)
+}
+
+/// Generate `<Name>Builder` and `<Name>::builder` for a struct with named fields.
+pub(crate) fn generate_builder(
+    input: &syn::DeriveInput,
+    options: &BuilderOptions,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "HtmlBuilder can only be derived for structs with named fields",
+        ));
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let builder = quote::format_ident!("{}Builder", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = fields
+        .named
+        .iter()
+        .map(parse_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // NOTE: with a typestate, every required field has a type parameter on the builder that is
+    //       a marker `Missing*` until it is set, then `()`. `finish` requires all of them to
+    //       implement a trait `Has*` that only `()` implements, so the error names the field.
+    let markers = quote::format_ident!("__yo_{}", name);
+    let states = fields
+        .iter()
+        .filter(|x| options.typestate && x.is_required())
+        .map(|x| {
+            let camel = to_camel_case(x.ident);
+            (
+                x.ident,
+                quote::format_ident!("__Yo{}", camel),
+                quote::format_ident!("Missing{}", camel),
+            )
+        })
+        .collect::<Vec<_>>();
+    let state_params = states.iter().map(|(_, state, _)| state).collect::<Vec<_>>();
+    let missing = states
+        .iter()
+        .map(|(_, _, marker)| marker)
+        .collect::<Vec<_>>();
+    let has = states
+        .iter()
+        .map(|(ident, _, _)| quote::format_ident!("Has{}", to_camel_case(ident)))
+        .collect::<Vec<_>>();
+    let missing_args = missing
+        .iter()
+        .map(|x| quote::quote! { #markers::#x })
+        .collect::<Vec<_>>();
+    let state_args = state_params
+        .iter()
+        .map(|x| quote::quote! { #x })
+        .collect::<Vec<_>>();
+
+    // NOTE: the states come right after the lifetimes so they don't need a default when the
+    //       other generic parameters have one, and the errors show them.
+    let (lifetimes, params): (Vec<_>, Vec<_>) = input
+        .generics
+        .params
+        .iter()
+        .partition(|x| matches!(x, syn::GenericParam::Lifetime(_)));
+    let lifetime_args = generic_args(&lifetimes);
+    let param_args = generic_args(&params);
+    let builder_type = |states: &[proc_macro2::TokenStream]| {
+        quote::quote! { #builder<#(#lifetime_args,)* #(#states,)* #(#param_args,)*> }
+    };
+    let impl_params = |states: &[&syn::Ident]| {
+        let lifetimes = lifetimes.iter().map(|x| strip_default(x));
+        let params = params.iter().map(|x| strip_default(x));
+        quote::quote! { <#(#lifetimes,)* #(#states,)* #(#params,)*> }
+    };
+    let where_clause = where_clause.map(|x| &x.predicates);
+
+    // NOTE: a typestate builder changes type so it can only be taken by value.
+    let by_value = options.typestate || options.owned;
+    let (receiver, output, finish_receiver) = if by_value {
+        (
+            quote::quote! { mut self },
+            quote::quote! { Self },
+            quote::quote! { self },
+        )
+    } else {
+        (
+            quote::quote! { &mut self },
+            quote::quote! { &mut Self },
+            quote::quote! { &mut self },
+        )
+    };
+
+    let mut storage = Vec::new();
+    let mut init = Vec::new();
+    let mut methods = Vec::new();
+    let mut values = Vec::new();
+    for field in &fields {
+        let Field {
+            ident, ty, kind, ..
+        } = field;
+        let set_attr = quote::format_ident!("set_attr_{}", ident);
+        let add_attr = quote::format_ident!("add_attr_{}", ident);
+        // NOTE: a builder taken by value is consumed by `finish` so its fields can be moved.
+        let (take_vec, take) = if by_value {
+            (quote::quote! { self.#ident }, quote::quote! { self.#ident })
+        } else {
+            (
+                quote::quote! { ::std::mem::take(&mut self.#ident) },
+                quote::quote! { self.#ident.take() },
+            )
+        };
+        let param_convert = |ty: &syn::Type| {
+            if field.options.into {
+                (
+                    quote::quote! { impl ::core::convert::Into<#ty> },
+                    quote::quote! { .into() },
+                )
+            } else {
+                (ty.to_token_stream(), quote::quote! {})
+            }
+        };
+
+        match kind {
+            FieldKind::Children(item) => {
+                // NOTE: the children are of many kinds (elements, text, ...) so they are always
+                //       converted.
+                storage.push(quote::quote! { #ident: ::std::vec::Vec<#item> });
+                init.push(quote::quote! { #ident: ::std::vec::Vec::new() });
+                methods.push(quote::quote! {
+                    #vis fn add_child(
+                        #receiver,
+                        child: impl ::core::convert::Into<#item>,
+                        additional: usize,
+                    ) -> #output {
+                        self.#ident.reserve(additional);
+                        self.#ident.push(child.into());
+                        self
+                    }
+                });
+                values.push(quote::quote! { #ident: #take_vec });
+            }
+            FieldKind::Vec(item) => {
+                let (param, convert) = param_convert(item);
+                storage.push(quote::quote! { #ident: ::std::vec::Vec<#item> });
+                init.push(quote::quote! { #ident: ::std::vec::Vec::new() });
+                methods.push(quote::quote! {
+                    #vis fn #set_attr(#receiver, value: #param) -> #output {
+                        self.#add_attr(value, 1)
+                    }
+
+                    #vis fn #add_attr(#receiver, value: #param, additional: usize) -> #output {
+                        self.#ident.reserve(additional);
+                        self.#ident.push(value #convert);
+                        self
+                    }
+                });
+                values.push(quote::quote! { #ident: #take_vec });
+            }
+            FieldKind::Option(inner) => {
+                let (param, convert) = param_convert(inner);
+                storage.push(quote::quote! { #ident: #ty });
+                init.push(quote::quote! { #ident: ::core::option::Option::None });
+                methods.push(quote::quote! {
+                    #vis fn #set_attr(#receiver, value: #param) -> #output {
+                        self.#ident = ::core::option::Option::Some(value #convert);
+                        self
+                    }
+                });
+                let value = match &field.options.default {
+                    Some(Some(default)) => quote::quote_spanned! {default.span()=>
+                        #take.or_else(|| ::core::option::Option::Some(#default))
+                    },
+                    _ => take,
+                };
+                values.push(quote::quote! { #ident: #value });
+            }
+            FieldKind::Required => {
+                let (param, convert) = param_convert(ty);
+                storage.push(quote::quote! { #ident: ::core::option::Option<#ty> });
+                init.push(quote::quote! { #ident: ::core::option::Option::None });
+                match states.iter().position(|(x, _, _)| x == ident) {
+                    Some(index) => {
+                        // NOTE: the builder is moved to a new type where this field is set.
+                        let mut states = state_args.clone();
+                        states[index] = quote::quote! { () };
+                        let output = builder_type(&states);
+                        let others = fields.iter().map(|x| x.ident).filter(|x| x != ident);
+                        methods.push(quote::quote! {
+                            #vis fn #set_attr(
+                                self,
+                                value: #param,
+                            ) -> #output {
+                                #builder {
+                                    #ident: ::core::option::Option::Some(value #convert),
+                                    #(#others: self.#others,)*
+                                    __yo_phantom: ::core::marker::PhantomData,
+                                }
+                            }
+                        });
+                    }
+                    None => methods.push(quote::quote! {
+                        #vis fn #set_attr(#receiver, value: #param) -> #output {
+                            self.#ident = ::core::option::Option::Some(value #convert);
+                            self
+                        }
+                    }),
+                }
+                let value = match &field.options.default {
+                    Some(Some(default)) => quote::quote_spanned! {default.span()=>
+                        #take.unwrap_or_else(|| #default)
+                    },
+                    Some(None) => quote::quote! { #take.unwrap_or_default() },
+                    None => {
+                        let message = format!("missing required attribute `{ident}`");
+                        quote::quote! { #take.expect(#message) }
+                    }
+                };
+                values.push(quote::quote! { #ident: #value });
+            }
+        }
+    }
+
+    // NOTE: the generated code starts the steps of a builder with `__yo_start`, this method takes
+    //       precedence over the convention of the context.
+    if by_value {
+        methods.push(quote::quote! {
+            #[doc(hidden)]
+            #vis fn __yo_start(self) -> Self {
+                self
+            }
+        });
+    }
+
+    let turbofish = ty_generics.as_turbofish();
+    let props = quote::quote! { #name #turbofish { #(#values,)* } };
+    let finish_where = quote::quote! { where #(#state_params: #markers::#has,)* };
+    let finish = match (&options.finish, &options.with) {
+        (Some(finish), Some(with)) => quote::quote! {
+            #vis fn finish(#finish_receiver) -> #finish #finish_where {
+                ::core::convert::Into::into((#with)(#props))
+            }
+        },
+        (Some(finish), None) => quote::quote! {
+            #vis fn finish(#finish_receiver) -> #finish #finish_where {
+                ::core::convert::Into::into(#props)
+            }
+        },
+        (None, _) => quote::quote! {
+            #vis fn finish(#finish_receiver) -> #name #ty_generics #finish_where {
+                #props
+            }
+        },
+    };
+
+    let markers_module = (!states.is_empty()).then(|| {
+        let on_unimplemented = states.iter().map(|(ident, _, _)| on_unimplemented(ident));
+        quote::quote! {
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            #vis mod #markers {
+                #(
+                    pub struct #missing;
+
+                    #on_unimplemented
+                    pub trait #has {}
+
+                    impl #has for () {}
+                )*
+            }
+        }
+    });
+
+    let builder_params = lifetimes
+        .iter()
+        .map(|x| x.to_token_stream())
+        .chain(state_params.iter().map(|x| x.to_token_stream()))
+        .chain(params.iter().map(|x| x.to_token_stream()));
+    let initial_builder = builder_type(&missing_args);
+    let any_builder = builder_type(&state_args);
+    let any_impl_params = impl_params(&state_params);
+
+    // NOTE: the generic parameters may not be used by all the fields of the builder.
+    Ok(quote::quote! {
+        #markers_module
+
+        #vis struct #builder<#(#builder_params),*>
+        where #where_clause
+        {
+            #(#storage,)*
+            __yo_phantom:
+                ::core::marker::PhantomData<fn() -> (#name #ty_generics, #(#state_params,)*)>,
+        }
+
+        impl #impl_generics #name #ty_generics where #where_clause {
+            #vis fn builder(_tag: &'static str) -> #initial_builder {
+                #builder {
+                    #(#init,)*
+                    __yo_phantom: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #any_impl_params #any_builder where #where_clause {
+            #(#methods)*
+
+            #finish
+        }
+    })
+}
+
+/// The error of a missing required field names it, `#[diagnostic::on_unimplemented]` is only
+/// available since Rust 1.78.
+#[rustversion::since(1.78)]
+fn on_unimplemented(ident: &syn::Ident) -> proc_macro2::TokenStream {
+    let message = format!("missing required attribute `{ident}`");
+    let label = format!("the attribute `{ident}` is required");
+    quote::quote! {
+        #[diagnostic::on_unimplemented(message = #message, label = #label)]
+    }
+}
+
+#[rustversion::before(1.78)]
+fn on_unimplemented(_ident: &syn::Ident) -> proc_macro2::TokenStream {
+    proc_macro2::TokenStream::new()
+}
+
+fn parse_field(field: &syn::Field) -> syn::Result<Field<'_>> {
+    let ident = field.ident.as_ref().expect("named field");
+    let ty = &field.ty;
+
+    let mut options = FieldOptions::default();
+    for attr in field.attrs.iter().filter(|x| x.path().is_ident("html")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                options.default = Some(
+                    meta.input
+                        .peek(syn::Token![=])
+                        .then(|| meta.value()?.parse())
+                        .transpose()?,
+                );
+            } else if meta.path.is_ident("into") {
+                options.into = true;
+            } else {
+                return Err(meta.error("unknown option, expected `default` or `into`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let kind = match generic_argument(ty) {
+        Some(("Vec", arg)) if ident == "children" => FieldKind::Children(arg.clone()),
+        _ if ident == "children" => {
+            return Err(syn::Error::new_spanned(ty, "the children must be a `Vec`"));
+        }
+        Some(("Vec", arg)) => FieldKind::Vec(arg.clone()),
+        Some(("Option", arg)) => FieldKind::Option(arg.clone()),
+        _ => FieldKind::Required,
+    };
+
+    if let (FieldKind::Children(_) | FieldKind::Vec(_), Some(_)) = (&kind, &options.default) {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "a `Vec` is always empty by default",
+        ));
+    }
+
+    Ok(Field {
+        ident,
+        ty,
+        kind,
+        options,
+    })
+}
+
+/// The name and the generic argument of types like `Vec<T>` and `Option<T>`.
+fn generic_argument(ty: &syn::Type) -> Option<(&'static str, &syn::Type)> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    let name = if segment.ident == "Vec" {
+        "Vec"
+    } else if segment.ident == "Option" {
+        "Option"
+    } else {
+        return None;
+    };
+    match arguments.args.first() {
+        Some(syn::GenericArgument::Type(arg)) if arguments.args.len() == 1 => Some((name, arg)),
+        _ => None,
+    }
+}
+
+/// The generic parameters used as arguments: `<'a, T: Clone = (), const N: usize>` gives
+/// `'a, T, N`.
+fn generic_args(params: &[&syn::GenericParam]) -> Vec<proc_macro2::TokenStream> {
+    params
+        .iter()
+        .map(|x| match x {
+            syn::GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+            syn::GenericParam::Type(param) => param.ident.to_token_stream(),
+            syn::GenericParam::Const(param) => param.ident.to_token_stream(),
+        })
+        .collect()
+}
+
+/// The defaults of the generic parameters can't be repeated on an `impl`.
+fn strip_default(param: &syn::GenericParam) -> syn::GenericParam {
+    let mut param = param.clone();
+    match &mut param {
+        syn::GenericParam::Type(param) => {
+            param.eq_token = None;
+            param.default = None;
+        }
+        syn::GenericParam::Const(param) => {
+            param.eq_token = None;
+            param.default = None;
+        }
+        syn::GenericParam::Lifetime(_) => {}
+    }
+    param
+}
+
+fn to_camel_case(ident: &syn::Ident) -> String {
+    use syn::ext::IdentExt;
+
+    ident
+        .unraw()
+        .to_string()
+        .split('_')
+        .map(|x| {
+            let mut chars = x.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
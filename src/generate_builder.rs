@@ -1,22 +1,93 @@
 use super::*;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+impl HtmlRoot {
+    /// Many elements at the root are wrapped in a fragment. Nothing is generated if no element
+    /// could be parsed.
+    ///
+    /// The context can make the builders `fallible`, `try_html!` always makes them `fallible`.
+    pub(crate) fn generate_builder(&self, fallible: bool) -> Option<proc_macro2::TokenStream> {
+        let has_option = |name: &str| {
+            self.context
+                .iter()
+                .flat_map(|x| &x.options)
+                .any(|x| x.name == name)
+        };
+        let opts = Options {
+            context: self
+                .context
+                .as_ref()
+                .map(|x| x.path.clone())
+                .unwrap_or_else(|| syn::parse_quote!(html_context)),
+            fallible: fallible || has_option("fallible"),
+        };
+        if !self.children.is_empty()
+            && self
+                .children
+                .iter()
+                .all(|x| matches!(x, HtmlElement::Invalid(_)))
+        {
+            return None;
+        }
+        let mut elements = self
+            .children
+            .iter()
+            .filter(|x| !matches!(x, HtmlElement::Let(_) | HtmlElement::Invalid(_)));
+        match (elements.next(), elements.next()) {
+            (Some(element), None) => Some(self.quote_single_element(&opts, element)),
+            _ => Some(quote_fragment(&opts, &self.children)),
+        }
+    }
+
+    /// A single element is not wrapped in a fragment, the let bindings around it are kept in a
+    /// block.
+    fn quote_single_element(
+        &self,
+        opts: &Options,
+        element: &HtmlElement,
+    ) -> proc_macro2::TokenStream {
+        let element = element.generate_builder(opts);
+        let mut locals = self.children.iter().filter_map(|x| match x {
+            HtmlElement::Let(local) => Some(local),
+            _ => None,
+        });
+        let position = self
+            .children
+            .iter()
+            .filter(|x| !matches!(x, HtmlElement::Invalid(_)))
+            .position(|x| !matches!(x, HtmlElement::Let(_)))
+            .unwrap_or_default();
+        let before = locals.by_ref().take(position).collect::<Vec<_>>();
+        let after = locals.collect::<Vec<_>>();
+        if before.is_empty() && after.is_empty() {
+            return element;
+        }
+
+        let value = proc_macro2::Ident::new("__yo_element", proc_macro2::Span::mixed_site());
+        quote::quote! {
+            {
+                #(#before)*
+                let #value = #element;
+                #(#after)*
+                #value
+            }
+        }
+    }
+}
 
 impl HtmlElement {
-    pub(crate) fn generate_builder(&self) -> proc_macro2::TokenStream {
-        use syn::spanned::Spanned;
+    pub(crate) fn generate_builder(&self, opts: &Options) -> proc_macro2::TokenStream {
         use HtmlElement::*;
 
         match self {
             Tagged(element) => {
-                let tag = &element.opening_tag.tag;
-                let tag_str = tag.to_string();
-                let generics = &element.opening_tag.generics;
-
                 let mut visited_attrs = Vec::new();
                 let attributes = element
                     .opening_tag
                     .attributes
                     .iter()
-                    .map(|x| {
+                    .flat_map(|x| {
                         let many;
                         let mut values = match &x.value {
                             Some(HtmlAttributeValue::Block(value)) => {
@@ -27,6 +98,13 @@ impl HtmlElement {
                                 many = false;
                                 vec![quote::ToTokens::to_token_stream(lit)]
                             }
+                            Some(HtmlAttributeValue::Format(format)) => {
+                                many = false;
+                                let args = &format.args;
+                                vec![quote::quote_spanned! {args.span()=>
+                                    format_args!(#args)
+                                }]
+                            }
                             Some(HtmlAttributeValue::ExprArray(array)) => {
                                 many = true;
                                 array
@@ -43,102 +121,717 @@ impl HtmlElement {
                         let mut attrs_count = vec![values.len()];
                         attrs_count.resize(values.len(), 0);
 
-                        match &x.name {
+                        // NOTE: an optional attribute is set from the content of the option.
+                        let value =
+                            proc_macro2::Ident::new("__yo_value", proc_macro2::Span::mixed_site());
+                        let optional = x.question_token.map(|_| {
+                            std::mem::replace(&mut values, vec![quote::quote! { #value }])
+                        });
+
+                        let (span, calls) = match &x.name {
                             HtmlAttributeName::Block(name) => {
                                 let name = quote_block(name);
-                                quote::quote_spanned! {name.span()=>
-                                    #(.add_attr(#name, #values, #attrs_count))*
-                                }
+                                let calls = values
+                                    .iter()
+                                    .zip(&attrs_count)
+                                    .map(|(value, count)| {
+                                        quote::quote_spanned! {name.span()=>
+                                            .add_attr(#name, #value, #count)
+                                        }
+                                    })
+                                    .collect();
+                                (name.span(), calls)
                             }
-                            HtmlAttributeName::Ident(name) => {
-                                if visited_attrs.contains(&name) {
+                            HtmlAttributeName::Name(name) => {
+                                let name_str = name.to_string();
+                                if visited_attrs.contains(&name_str) {
                                     values.clear();
-                                    values.push(quote::quote_spanned! {name.span()=>
-                                        compile_error!("attribute already defined")
-                                    });
+                                    values.push(
+                                        syn::Error::new_spanned(name, "attribute already defined")
+                                            .to_compile_error(),
+                                    );
                                 }
-                                visited_attrs.push(name);
-                                if many {
-                                    let method = quote::format_ident!("add_attr_{}", name);
-                                    quote::quote_spanned! {name.span()=>
-                                        #(.#method(#values, #attrs_count))*
+                                visited_attrs.push(name_str.clone());
+                                let calls = if x.value.is_none() {
+                                    // NOTE: a flag has no value to carry the error.
+                                    if let Some(error) = values.pop() {
+                                        return vec![BuilderStep::Stmt(error)];
+                                    } else if name.is_dynamic() {
+                                        vec![quote::quote_spanned! {name.span()=>
+                                            .add_flag(#name_str)
+                                        }]
+                                    } else {
+                                        let method =
+                                            quote::format_ident!("set_flag_{}", name.to_ident());
+                                        vec![quote::quote_spanned! {name.span()=>
+                                            .#method()
+                                        }]
                                     }
+                                } else if name.is_dynamic() {
+                                    values
+                                        .iter()
+                                        .zip(&attrs_count)
+                                        .map(|(value, count)| {
+                                            quote::quote_spanned! {name.span()=>
+                                                .add_attr(#name_str, #value, #count)
+                                            }
+                                        })
+                                        .collect()
+                                } else if many {
+                                    let method =
+                                        quote::format_ident!("add_attr_{}", name.to_ident());
+                                    values
+                                        .iter()
+                                        .zip(&attrs_count)
+                                        .map(|(value, count)| {
+                                            quote::quote_spanned! {name.span()=>
+                                                .#method(#value, #count)
+                                            }
+                                        })
+                                        .collect()
                                 } else {
-                                    let method = quote::format_ident!("set_attr_{}", name);
-                                    quote::quote_spanned! {name.span()=>
-                                        #(.#method(#values))*
-                                    }
-                                }
+                                    let method =
+                                        quote::format_ident!("set_attr_{}", name.to_ident());
+                                    values
+                                        .iter()
+                                        .map(|value| {
+                                            quote::quote_spanned! {name.span()=>
+                                                .#method(#value)
+                                            }
+                                        })
+                                        .collect()
+                                };
+                                (name.span(), calls)
                             }
                             HtmlAttributeName::Shorthand { ident, .. } => {
                                 let mut name = quote::ToTokens::to_token_stream(&ident);
-                                if visited_attrs.contains(&ident) {
+                                let name_str = ident.unraw().to_string();
+                                if visited_attrs.contains(&name_str) {
                                     name = quote::quote_spanned! {ident.span()=>
                                         compile_error!("attribute already defined")
                                     };
                                 }
-                                visited_attrs.push(ident);
+                                visited_attrs.push(name_str);
                                 let method = quote::format_ident!("set_attr_{}", ident);
-                                quote::quote_spanned! {ident.span()=>
+                                let call = quote::quote_spanned! {ident.span()=>
                                     .#method(#name)
-                                }
+                                };
+                                (ident.span(), vec![call])
                             }
+                            HtmlAttributeName::Spread { expr, .. } => {
+                                let expr = match expr.as_ref() {
+                                    syn::Expr::Block(expr) => quote_block(&expr.block),
+                                    expr => quote::ToTokens::to_token_stream(expr),
+                                };
+                                let call = quote::quote_spanned! {expr.span()=>
+                                    .spread_attrs(#expr)
+                                };
+                                (expr.span(), vec![call])
+                            }
+                        };
+                        let steps = calls.into_iter().map(|call| BuilderStep::Call(span, call));
+                        match optional {
+                            Some(option) => {
+                                let calls = steps.map(|x| x.into_stmt(opts));
+                                vec![BuilderStep::Stmt(quote::quote! {
+                                    if let ::core::option::Option::Some(#value) = #(#option)* {
+                                        #(#calls)*
+                                    }
+                                })]
+                            }
+                            None => steps.collect(),
                         }
                     })
                     .collect::<Vec<_>>();
 
-                let children = element
-                    .children
-                    .iter()
-                    .enumerate()
-                    .map(|(i, x)| {
-                        let children_count = if i == 0 { element.children.len() } else { 0 };
-                        let child = x.generate_builder();
-                        quote::quote_spanned! {child.span()=>
-                            .add_child(#child, #children_count)
-                        }
-                    })
-                    .collect::<Vec<_>>();
+                let children = generate_static_children(opts, &element.children);
 
-                quote::quote! {
-                    <#tag #generics>::builder(#tag_str)
-                        #(#attributes)*
-                        #(#children)*
-                        .finish()
-                }
-            }
-            Fragmented(fragment) => {
-                let children = fragment
-                    .children
-                    .iter()
-                    .enumerate()
-                    .map(|(i, x)| {
-                        let children_count = if i == 0 { fragment.children.len() } else { 0 };
-                        let child = x.generate_builder();
-                        quote::quote_spanned! {child.span()=>
-                            .add_child(#child, #children_count)
-                        }
-                    })
-                    .collect::<Vec<_>>();
-
-                quote::quote! {
-                    Fragment::builder("")
-                        #(#children)*
-                        .finish()
-                }
+                quote_builder(
+                    opts,
+                    element.opening_tag.tag.span(),
+                    element.opening_tag.tag.to_builder(opts),
+                    attributes.into_iter().chain(children),
+                )
             }
+            Fragmented(fragment) => quote_fragment(opts, &fragment.children),
             Block(block) => quote_block(block),
             Format(format) => {
+                let context = &opts.context;
                 let args = &format.args;
 
                 quote::quote_spanned! {args.span()=>
-                    Text::from(format_args!(#args))
+                    #context::Text::from(format_args!(#args))
+                }
+            }
+            Lit(lit) => {
+                let context = &opts.context;
+                quote::quote_spanned! {lit.span()=>
+                    #context::Text::from(#lit)
+                }
+            }
+            #[cfg(feature = "jsx-text")]
+            Text(text) => {
+                let context = &opts.context;
+                let text = &text.text;
+                quote::quote_spanned! {text.span()=>
+                    #context::Text::from(#text)
+                }
+            }
+            // NOTE: control flow can only add children to a builder, so when it is used on its own
+            //       it gets wrapped in a fragment.
+            If(_) | For(_) | Match(_) | Let(_) | Invalid(_) => {
+                quote_fragment(opts, std::slice::from_ref(self))
+            }
+        }
+    }
+
+    /// Generate the builder step that adds this element as a child of the enclosing builder.
+    ///
+    /// `remaining` is the number of children that will be added after this one, it is used by
+    /// loops to reserve enough space for the children that follow them. The control flow stores
+    /// the part of the reservation hint it didn't use in a variable for the next child.
+    fn generate_child(
+        &self,
+        opts: &Options,
+        children_count: &proc_macro2::TokenStream,
+        remaining: usize,
+    ) -> BuilderStep {
+        use HtmlElement::*;
+
+        let hint = hint_ident();
+        let control_flow = match self {
+            If(element) => element.generate_child(opts, children_count, remaining),
+            For(element) => element.generate_child(opts, remaining),
+            Match(element) => element.generate_child(opts, children_count, remaining),
+            Let(local) => return BuilderStep::Stmt(quote::ToTokens::to_token_stream(local)),
+            _ => {
+                let child = self.generate_builder(opts);
+                return BuilderStep::Call(
+                    self.span(),
+                    quote::quote_spanned! {child.span()=>
+                        .add_child(#child, #children_count)
+                    },
+                );
+            }
+        };
+        BuilderStep::Stmt(quote::quote! {
+            let #hint = #control_flow;
+        })
+    }
+
+    fn is_control_flow(&self) -> bool {
+        matches!(self, Self::If(_) | Self::For(_) | Self::Match(_))
+    }
+
+    /// The reservation hint left by the element when it is the last one of a branch: only the
+    /// control flow can leave one.
+    fn leftover_hint(&self) -> proc_macro2::TokenStream {
+        if self.is_control_flow() {
+            let hint = hint_ident();
+            quote::quote! { #hint }
+        } else {
+            quote::quote! { 0usize }
+        }
+    }
+
+    /// The span of the element in the template, the steps that add it to the enclosing builder
+    /// are spanned on it.
+    fn span(&self) -> proc_macro2::Span {
+        use HtmlElement::*;
+
+        match self {
+            Tagged(element) => element.opening_tag.tag.span(),
+            Fragmented(fragment) => fragment.opening_fragment.opening_bracket_token.span,
+            Block(block) => block.brace_token.span.join(),
+            Format(format) => format.paren_token.span.join(),
+            Lit(lit) => lit.span(),
+            #[cfg(feature = "jsx-text")]
+            Text(text) => text.text.span(),
+            If(_) | For(_) | Match(_) | Let(_) | Invalid(_) => proc_macro2::Span::call_site(),
+        }
+    }
+
+    /// The number of children added to the enclosing builder that is known at compile time.
+    fn static_children_count(&self) -> usize {
+        match self {
+            HtmlElement::For(_) | HtmlElement::Let(_) | HtmlElement::Invalid(_) => 0,
+            _ => 1,
+        }
+    }
+}
+
+impl HtmlElementIf {
+    fn generate_child(
+        &self,
+        opts: &Options,
+        children_count: &proc_macro2::TokenStream,
+        remaining: usize,
+    ) -> proc_macro2::TokenStream {
+        let cond = &self.cond;
+        let then_branch = self
+            .then_branch
+            .generate_child(opts, children_count, remaining);
+        let else_branch = match &self.else_branch {
+            Some((_, HtmlElementElse::If(element))) => {
+                element.generate_child(opts, children_count, remaining)
+            }
+            Some((_, HtmlElementElse::Branch(branch))) => {
+                let branch = branch.generate_child(opts, children_count, remaining);
+                quote::quote! { { #branch } }
+            }
+            None => {
+                // NOTE: the empty fragment keeps the children count of the parent accurate.
+                let fragment = quote_fragment(opts, &[]);
+                let add_child = BuilderStep::Call(
+                    proc_macro2::Span::call_site(),
+                    quote::quote! {
+                        .add_child(#fragment, #children_count)
+                    },
+                )
+                .into_stmt(opts);
+                quote::quote! { { #add_child 0usize } }
+            }
+        };
+
+        quote::quote! {
+            if #cond {
+                #then_branch
+            } else #else_branch
+        }
+    }
+}
+
+impl HtmlBranch {
+    /// A branch is wrapped in a fragment unless it has a single element (not counting the let
+    /// bindings), which is added as it is. So a branch produces one child, except when its element
+    /// is a loop: the items are then added directly and the loop reserves space for them and for
+    /// the `remaining` children that follow the branch. The branch evaluates to the reservation
+    /// hint its element didn't use.
+    fn generate_child(
+        &self,
+        opts: &Options,
+        children_count: &proc_macro2::TokenStream,
+        remaining: usize,
+    ) -> proc_macro2::TokenStream {
+        let mut elements = self
+            .children
+            .iter()
+            .filter(|x| !matches!(x, HtmlElement::Let(_) | HtmlElement::Invalid(_)));
+        match (elements.next(), elements.next()) {
+            (Some(element), None) => {
+                let children = generate_children(opts, &self.children, children_count, remaining)
+                    .into_iter()
+                    .map(|x| x.into_stmt(opts));
+                let hint = element.leftover_hint();
+                quote::quote! { #(#children)* #hint }
+            }
+            _ => {
+                let fragment = quote_fragment(opts, &self.children);
+                let add_child = BuilderStep::Call(
+                    self.brace_token.span.join(),
+                    quote::quote_spanned! {fragment.span()=>
+                        .add_child(#fragment, #children_count)
+                    },
+                )
+                .into_stmt(opts);
+                quote::quote! { #add_child 0usize }
+            }
+        }
+    }
+}
+
+impl HtmlElementFor {
+    /// The children of the loop's body are added straight to the enclosing builder. The first
+    /// iteration reserves space for the items announced by the iterator's `size_hint` and for the
+    /// children that follow the loop. The loop evaluates to the reservation hint that was not used
+    /// because it had no iteration.
+    fn generate_child(&self, opts: &Options, remaining: usize) -> proc_macro2::TokenStream {
+        let pat = &self.pat;
+        let expr = &self.expr;
+        let iter = proc_macro2::Ident::new("__yo_iter", proc_macro2::Span::mixed_site());
+        let additional =
+            proc_macro2::Ident::new("__yo_additional", proc_macro2::Span::mixed_site());
+        let per_iteration: usize = self
+            .body
+            .children
+            .iter()
+            .map(HtmlElement::static_children_count)
+            .sum();
+        let children =
+            generate_children(opts, &self.body.children, &quote::quote! { #additional }, 0)
+                .into_iter()
+                .map(|x| x.into_stmt(opts));
+
+        quote::quote! {
+            {
+                let #iter = ::core::iter::IntoIterator::into_iter(#expr);
+                let mut #additional = ::core::iter::Iterator::size_hint(&#iter)
+                    .0
+                    .saturating_mul(#per_iteration)
+                    .saturating_add(#remaining);
+                for #pat in #iter {
+                    #(#children)*
+                    #additional = 0;
                 }
+                #additional
             }
         }
     }
 }
 
+impl HtmlElementMatch {
+    /// Every arm adds exactly one child and evaluates to the reservation hint it didn't use. The
+    /// patterns and guards are kept as they are.
+    fn generate_child(
+        &self,
+        opts: &Options,
+        children_count: &proc_macro2::TokenStream,
+        remaining: usize,
+    ) -> proc_macro2::TokenStream {
+        let expr = &self.expr;
+        let arms = self.arms.iter().map(|arm| {
+            let pat = &arm.pat;
+            let guard = arm
+                .guard
+                .as_ref()
+                .map(|(if_token, guard)| quote::quote! { #if_token #guard });
+            let fat_arrow_token = &arm.fat_arrow_token;
+            let body = arm
+                .body
+                .generate_child(opts, children_count, remaining)
+                .into_stmt(opts);
+            let hint = arm.body.leftover_hint();
+            quote::quote! {
+                #pat #guard #fat_arrow_token {
+                    #body
+                    #hint
+                }
+            }
+        });
+
+        quote::quote! {
+            match #expr {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+impl HtmlTagName {
+    /// Create the builder: the elements are looked up in the context where custom elements and
+    /// namespaced elements have their name mangled, dynamic tags use the generic `DynamicElement`
+    /// and the components (capitalized or with a path) are looked up in the caller's scope.
+    fn to_builder(&self, opts: &Options) -> proc_macro2::TokenStream {
+        let context = &opts.context;
+        match self {
+            Self::Path(path) => {
+                // NOTE: the name given to the builder is the last segment of the path.
+                let tag_str = path
+                    .segments
+                    .last()
+                    .map(|x| x.ident.unraw().to_string())
+                    .unwrap_or_default();
+                let is_element = path.leading_colon.is_none()
+                    && path.segments.len() == 1
+                    && !tag_str.starts_with(char::is_uppercase);
+                if is_element {
+                    quote::quote! { <#context::#path>::builder(#tag_str) }
+                } else {
+                    quote::quote! { <#path>::builder(#tag_str) }
+                }
+            }
+            Self::Name(name) => {
+                let tag = name.to_ident();
+                let tag_str = name.to_string();
+                quote::quote! { <#context::#tag>::builder(#tag_str) }
+            }
+            Self::Block(block) => {
+                let tag = quote_block(block);
+                quote::quote_spanned! {block.span()=>
+                    #context::DynamicElement::builder(#tag)
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for HtmlTagName {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Path(path) => {
+                if path.leading_colon.is_some() {
+                    write!(f, "::")?;
+                }
+                for pair in path.segments.pairs() {
+                    let segment = pair.value();
+                    write!(f, "{}", segment.ident)?;
+                    if !segment.arguments.is_empty() {
+                        let arguments = quote::ToTokens::to_token_stream(&segment.arguments);
+                        write!(f, "{}", arguments.to_string().replace(' ', ""))?;
+                    }
+                    if pair.punct().is_some() {
+                        write!(f, "::")?;
+                    }
+                }
+                Ok(())
+            }
+            Self::Name(name) => name.fmt(f),
+            Self::Block(block) => {
+                let stmts = block.stmts.iter().map(quote::ToTokens::to_token_stream);
+                write!(f, "{{{}}}", quote::quote! { #(#stmts)* })
+            }
+        }
+    }
+}
+
+impl HtmlName {
+    /// Names starting with `data-` or `aria-` can't be known in advance by a builder so they are
+    /// always passed to `add_attr`.
+    fn is_dynamic(&self) -> bool {
+        matches!(
+            self.segments.pairs().next(),
+            Some(syn::punctuated::Pair::Punctuated(first, HtmlNameSeparator::Dash(_)))
+                if first == "data" || first == "aria"
+        )
+    }
+
+    /// The name in a form that can be used in a type or method name: `hx-get` becomes `hx_get`,
+    /// `svg:rect` becomes `svg_rect` and `r#type` becomes `type`.
+    fn to_ident(&self) -> syn::Ident {
+        let ident = self
+            .segments
+            .iter()
+            .map(|x| x.unraw().to_string())
+            .collect::<Vec<_>>()
+            .join("_");
+        syn::Ident::new(&ident, self.span())
+    }
+}
+
+impl std::fmt::Display for HtmlName {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for pair in self.segments.pairs() {
+            write!(f, "{}", pair.value().unraw())?;
+            match pair.punct() {
+                Some(HtmlNameSeparator::Dash(_)) => write!(f, "-")?,
+                Some(HtmlNameSeparator::Colon(_)) => write!(f, ":")?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A step of a builder, either chained with the previous ones or applied on the builder's
+/// variable. A call has the span of the part of the template that produced it.
+enum BuilderStep {
+    Call(proc_macro2::Span, proc_macro2::TokenStream),
+    Stmt(proc_macro2::TokenStream),
+}
+
+impl BuilderStep {
+    /// A call is applied on the builder's variable and assigned back to it, the variable holds
+    /// either the builder or a mutable reference to it (see [`quote_start`]).
+    fn into_stmt(self, opts: &Options) -> proc_macro2::TokenStream {
+        match self {
+            Self::Call(span, call) => {
+                let builder = builder_ident();
+                let call = quote_call(opts, span, quote::quote! { #builder }, call);
+                quote::quote! { #builder = #call; }
+            }
+            Self::Stmt(stmt) => stmt,
+        }
+    }
+}
+
+/// The first child (let bindings excluded) receives `children_count` as reservation hint for all
+/// the children, the child following a control flow receives the hint it didn't use. `remaining`
+/// is the number of children added after these ones. The elements that could not be parsed are
+/// skipped.
+fn generate_children(
+    opts: &Options,
+    children: &[HtmlElement],
+    children_count: &proc_macro2::TokenStream,
+    remaining: usize,
+) -> Vec<BuilderStep> {
+    let mut remaining: usize = remaining
+        + children
+            .iter()
+            .map(HtmlElement::static_children_count)
+            .sum::<usize>();
+    let mut hint = Some(children_count.clone());
+    children
+        .iter()
+        .filter(|x| !matches!(x, HtmlElement::Invalid(_)))
+        .map(|x| {
+            remaining -= x.static_children_count();
+            if matches!(x, HtmlElement::Let(_)) {
+                return x.generate_child(opts, children_count, remaining);
+            }
+            let children_count = hint.take().unwrap_or_else(|| quote::quote! { 0usize });
+            let step = x.generate_child(opts, &children_count, remaining);
+            if x.is_control_flow() {
+                let leftover = hint_ident();
+                hint = Some(quote::quote! { #leftover });
+            }
+            step
+        })
+        .collect()
+}
+
+fn generate_static_children(opts: &Options, children: &[HtmlElement]) -> Vec<BuilderStep> {
+    let children_count: usize = children
+        .iter()
+        .map(HtmlElement::static_children_count)
+        .sum();
+    generate_children(opts, children, &quote::quote! { #children_count }, 0)
+}
+
+fn quote_fragment(opts: &Options, children: &[HtmlElement]) -> proc_macro2::TokenStream {
+    let context = &opts.context;
+    quote_builder(
+        opts,
+        proc_macro2::Span::call_site(),
+        quote::quote! { #context::Fragment::builder("") },
+        generate_static_children(opts, children),
+    )
+}
+
+/// Chain all the steps on the builder when possible, otherwise store the builder in a variable
+/// and apply the steps one by one.
+///
+/// Every step before a statement is rebound to the variable so it can return the builder by value
+/// (like a typestate builder that changes type at every attribute) as well as by reference.
+/// `finish` and its receiver are spanned on the tag so an error about a builder that can't be
+/// finished points at it: the receiver is bound by a `match`, which keeps the temporary builder
+/// of a chain alive.
+fn quote_builder(
+    opts: &Options,
+    span: proc_macro2::Span,
+    builder: proc_macro2::TokenStream,
+    steps: impl IntoIterator<Item = BuilderStep>,
+) -> proc_macro2::TokenStream {
+    let var = builder_ident();
+    let mut calls = Vec::new();
+    let mut stmts = Vec::new();
+    for step in steps {
+        match step {
+            BuilderStep::Call(span, call) => calls.push((span, call)),
+            BuilderStep::Stmt(stmt) => {
+                stmts.extend(calls.drain(..).map(|(span, call)| {
+                    let call = quote_call(opts, span, quote::quote! { #var }, call);
+                    quote::quote! {
+                        let mut #var = #call;
+                    }
+                }));
+                stmts.push(stmt);
+            }
+        }
+    }
+    let finish = |receiver: proc_macro2::TokenStream| {
+        let receiver = calls.into_iter().fold(receiver, |receiver, (span, call)| {
+            quote_call(opts, span, receiver, call)
+        });
+        let finished = proc_macro2::Ident::new("__yo_finished", span);
+        let finish = quote_call(
+            opts,
+            span,
+            quote::quote! { #finished },
+            quote::quote_spanned! {span=> .finish() },
+        );
+        quote::quote! {
+            match #receiver {
+                #[allow(unused_mut)]
+                mut #finished => #finish,
+            }
+        }
+    };
+
+    if stmts.is_empty() {
+        return finish(builder);
+    }
+
+    let start = quote_start(opts);
+    let finish = finish(quote::quote! { #var });
+    quote::quote! {
+        {
+            let mut #var = #builder;
+            let mut #var = #start;
+            #(#stmts)*
+            #finish
+        }
+    }
+}
+
+/// Take the builder's variable by value when the context declares `OWNED_BUILDERS = true`, or a
+/// mutable reference to it otherwise, so every step can be assigned back to the variable.
+///
+/// The constant is looked up with a glob import that shadows the default `false`. The method
+/// `__yo_start` is then implemented by value only if the constant is `true`, otherwise the call
+/// falls back to the implementation by reference. A builder that has its own `__yo_start` method
+/// (like the derived builders that take `self`) keeps its convention in any context.
+fn quote_start(opts: &Options) -> proc_macro2::TokenStream {
+    let context = &opts.context;
+    let var = builder_ident();
+    quote::quote! {
+        {
+            const __YO_OWNED: bool = {
+                #[allow(dead_code)]
+                const OWNED_BUILDERS: bool = false;
+                {
+                    #[allow(unused_imports)]
+                    use #context::*;
+                    OWNED_BUILDERS
+                }
+            };
+            #[allow(dead_code)]
+            struct __YoContext<const OWNED: bool>;
+            trait __YoOwnedContext<T> {}
+            impl<T> __YoOwnedContext<T> for __YoContext<true> {}
+            trait __YoBorrowed {
+                fn __yo_start(&mut self) -> &mut Self {
+                    self
+                }
+            }
+            impl<T> __YoBorrowed for T {}
+            trait __YoOwned: Sized {
+                fn __yo_start(self) -> Self {
+                    self
+                }
+            }
+            impl<T> __YoOwned for T where __YoContext<{ __YO_OWNED }>: __YoOwnedContext<T> {}
+            #var.__yo_start()
+        }
+    }
+}
+
+/// Apply a call to a builder. The result of a fallible builder is unwrapped with `?`: the call is
+/// wrapped in parentheses spanned with the `?` on the part of the template that produced it, so
+/// an error about the conversion of the error points there instead of at the whole macro.
+fn quote_call(
+    opts: &Options,
+    span: proc_macro2::Span,
+    receiver: proc_macro2::TokenStream,
+    call: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if opts.fallible {
+        quote::quote_spanned! {span=> (#receiver #call)? }
+    } else {
+        quote::quote! { #receiver #call }
+    }
+}
+
+/// The variable holding a builder when its steps can't be chained. The span prevents any
+/// collision with the user's code.
+fn builder_ident() -> proc_macro2::Ident {
+    proc_macro2::Ident::new("__yo_builder", proc_macro2::Span::mixed_site())
+}
+
+/// The variable holding the reservation hint left by a control flow for the next child.
+fn hint_ident() -> proc_macro2::Ident {
+    proc_macro2::Ident::new("__yo_hint", proc_macro2::Span::mixed_site())
+}
+
 fn quote_block(block: &syn::Block) -> proc_macro2::TokenStream {
     if block.stmts.len() == 1 {
         quote::ToTokens::to_token_stream(&block.stmts[0])
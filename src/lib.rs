@@ -13,49 +13,380 @@
 //!             <li {dynamic_attribute}="color:red"></li>
 //!             <li>("%x", 42)</li>     // Shorthand for: format_args!("%x", 42)
 //!         </ul>
+//!         if name.is_empty() { <p>{"Nobody"}</p> } else { <p>{name}</p> }
+//!         <ol>for i in 0..3 { let n = i + 1; <li>("Item #{}", n)</li> }</ol>
+//!         match name.len() { 0 => {"Empty"}, n => <p>("{} letters", n)</p> }
 //!     </>
 //! }
 //! ```
 //!
+//! Many elements at the root are wrapped in a fragment.
+//!
+//! String, character and number literals can be used as children without braces:
+//! `<p>"Hello " <b>"world"</b></p>`. They are built with `html_context::Text::from(lit)`.
+//!
+//! With the `jsx-text` feature (Rust 1.88+), text can be written without quotes: `<p>Hello world</p>`.
+//! The text goes up to the next tag, block, literal or format shorthand `(...)` and its whitespace
+//! is collapsed like in JSX. It must still be made of valid Rust tokens so quotes and apostrophes
+//! can't be used, and parentheses must be in a literal. Text can't start with `if`, `for`, `match`
+//! or `let` either, these are always parsed as control flow: `<p>"for" you</p>`.
+//!
+//! ## Elements
+//!
+//! An element `<div>` is built with `html_context::div::builder("div")`, the module
+//! `html_context` must be in scope. The dashes and colons of custom elements and namespaced elements
+//! are replaced by underscores: `<my-widget>` calls `html_context::my_widget::builder("my-widget")`
+//! and `<svg:rect>` calls `html_context::svg_rect::builder("svg:rect")`.
+//!
+//! Another context module can be chosen for a call: `html!(in crate::ssr => <div />)` calls
+//! `crate::ssr::div::builder("div")`. `Text`, `Fragment` and `DynamicElement` are looked up in the
+//! context module too.
+//!
+//! By default the builders are borrowed: their methods take `&mut self`. A context module declares
+//! that its builders are owned, taken and returned by value like immutable builders, with a
+//! constant:
+//!
+//! ```ignore
+//! pub mod ssr {
+//!     pub const OWNED_BUILDERS: bool = true;
+//!     pub use crate::elements::*;
+//! }
+//! ```
+//!
+//! The generated code then assigns `builder = builder.add_child(child, 0)` in loops and conditions
+//! and `finish` can take `self`.
+//!
+//! Components are capitalized or referred to by path and are looked up in the caller's scope. They
+//! can have generic arguments: `<ui::Card<T>>` calls `<ui::Card<T>>::builder("Card")` and must be
+//! closed by `</ui::Card>` or `</ui::Card<T>>`.
+//!
+//! A tag can be chosen at runtime with a block: `<{tag}>` calls
+//! `html_context::DynamicElement::builder(tag)` and is closed by `</{tag}>` or `</>`.
+//!
+//! ## Attributes
+//!
+//! An attribute `name=value` is passed to the builder with `.set_attr_name(value)` and an
+//! array of values `name=[a, b]` with `.add_attr_name(a, 2).add_attr_name(b, 0)`. An attribute
+//! with a dynamic name `{name}=value` is passed with `.add_attr(name, value, 1)`.
+//!
+//! The value can use the same shorthand as the children to format text without allocating:
+//! `title=("Item #{}", id)` calls `.set_attr_title(format_args!("Item #{}", id))`.
+//!
+//! Hyphenated and namespaced names have their dashes and colons replaced by underscores:
+//! `hx-get="/"` calls `.set_attr_hx_get("/")` and `xlink:href="#"` calls
+//! `.set_attr_xlink_href("#")`. The names starting with `data-` or `aria-` can't be known in
+//! advance so they are always dynamic: `data-id={id}` calls `.add_attr("data-id", id, 1)`.
+//!
+//! An attribute without value is a flag: `disabled` calls `.set_flag_disabled()` and `data-new`
+//! calls `.add_flag("data-new")`.
+//!
+//! An optional attribute `name?={value}` is only set when the value is `Some`:
+//! `title?={title}` calls `.set_attr_title(v)` if `title` is `Some(v)`.
+//!
+//! The attributes of a value can be spread with `..props` or `..{expr}` which calls
+//! `.spread_attrs(props)`. The attributes are applied in order so the ones after the spread
+//! override it. The path can be followed by field accesses, method calls, indexing and `?`:
+//! `..props.attrs()`, any other expression must be in a block.
+//!
+//! Rust keywords and raw identifiers can be used as names too: `type="text"` and `r#type="text"`
+//! both call `.set_attr_type("text")`.
+//!
+//! ## Frameworks
+//!
+//! A framework can define its own macro with a fixed context module, so its users don't need to
+//! import it:
+//!
+//! ```ignore
+//! yo_html::define_html_macro!(pub my_html => crate::html_context);
+//! ```
+//!
+//! A public macro must be defined at the root of the crate: it calls `html!` through a hidden
+//! re-export so the users of the framework don't need to depend on yo-html. A public macro can
+//! only be used by its name in the crate itself. `pub(crate)` and private macros can be defined
+//! anywhere.
+//!
+//! The options of the context are given after it:
+//! `define_html_macro!(pub ssr => crate::ssr, fallible)`.
+//!
+//! ## Fallible builders
+//!
+//! `try_html!` is used like `html!` for builders that return a `Result`: every step and `finish`
+//! are followed by `?`, so the errors are propagated by the enclosing function after being
+//! converted with `From`. An error that can't be converted is reported on the attribute or the
+//! child that produced the step, or on the tag for `finish`.
+//!
+//! ```ignore
+//! fn render(id: &str) -> Result<Markup, RenderError> {
+//!     Ok(try_html! { in crate::ssr => <div {id}>"Hello"</div> })
+//! }
+//! ```
+//!
+//! The context can be declared `fallible` instead, for a framework's macro:
+//! `define_html_macro!(pub try_ssr => crate::ssr, fallible)`.
+//!
+//! ## Components
+//!
+//! The builder of a component can be derived from its props:
+//!
+//! ```ignore
+//! #[derive(yo_html::HtmlBuilder)]
+//! #[html(finish = VNode, with = VNodePureComponent::new)]
+//! struct Card {
+//!     #[html(into)]
+//!     title: String,
+//!     #[html(default = 1)]
+//!     level: u8,
+//!     subtitle: Option<String>,
+//!     class: Vec<String>,
+//!     children: Vec<VNode>,
+//! }
+//! ```
+//!
+//! `Card::builder` returns a `CardBuilder` with a `set_attr_*` method per field, an `add_attr_*`
+//! method per `Vec` field and `add_child` for the field `children`, which accepts anything that
+//! converts into its items. The fields are required unless
+//! they are an `Option`, a `Vec` or have a default: `#[html(default)]` uses `Default::default()`
+//! and `#[html(default = 10)]` uses `10`, which is wrapped in `Some` for an `Option`.
+//! `#[html(into)]` makes the methods accept anything that converts into the field's type.
+//!
+//! `finish` returns the props, or converts them with `Into` to the type given by
+//! `#[html(finish = Type)]`, after passing them to the function `with` if any.
+//!
+//! With `#[html(typestate)]` the builder tracks the required fields in its type, a missing one is a
+//! compile error on the tag instead of a panic, which names it since Rust 1.78. All the methods then
+//! take the builder by value.
+//!
+//! With `#[html(owned)]` all the methods take the builder by value too. A builder taken by value
+//! can be used in any context, whether its builders are owned or not.
+//!
+//! A function can be turned into a component with `#[component]`:
+//!
+//! ```ignore
+//! #[yo_html::component]
+//! fn Card(#[html(into)] title: IString, subtitle: Option<IString>, children: VNode) -> VNode {
+//!     html! { <div>{title}{subtitle}{children}</div> }
+//! }
+//! ```
+//!
+//! The arguments become the fields of a props struct named after the function and accept the same
+//! attributes as the derive. The children are collected into the type of `children` with
+//! `FromIterator`. The builder tracks the required props in its type so a missing one is a compile
+//! error on the tag: ``no method named `finish` found for struct `CardBuilder<MissingTitle>` ``.
+//!
+//! `finish` calls the function. With `#[component(wrap = FnComponent::new)]` it returns
+//! `FnComponent::new(props, render)` instead, where `render` renders a reference to the props by
+//! cloning them. The `#[derive]` attributes of the function are applied to the props.
+//!
 //! An example of web framework is provided in the `examples` directory but you need to make your
 //! own for this macro to be usable.
 
+mod component;
+mod derive;
 mod generate_builder;
 mod parser;
 
 #[proc_macro]
 pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    if input.is_empty() {
-        return quote::quote! {
-            {
-                use html_context::*;
+    let root = syn::parse_macro_input!(input as HtmlRoot);
+    expand(root, false).into()
+}
 
-                Fragment::builder("").finish()
-            }
-        }
-        .into();
-    }
+/// Like `html!` but the builders are fallible: `?` is applied after every step and `finish`.
+///
+/// See the [crate documentation](crate#fallible-builders).
+#[proc_macro]
+pub fn try_html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let root = syn::parse_macro_input!(input as HtmlRoot);
+    expand(root, true).into()
+}
 
-    // Parse the input tokens into a syn AST
-    let item = syn::parse_macro_input!(input as HtmlElement);
+fn expand(root: HtmlRoot, fallible: bool) -> proc_macro2::TokenStream {
+    // NOTE: the errors are wrapped in a block because there can be many of them and this macro is
+    //       used in expression position.
+    let errors = root
+        .errors()
+        .into_iter()
+        .reduce(|mut errors, err| {
+            errors.combine(err);
+            errors
+        })
+        .map(|err| err.to_compile_error());
 
-    let builder = item.generate_builder();
+    let Some(builder) = root.generate_builder(fallible) else {
+        return quote::quote! { { #errors } };
+    };
 
     quote::quote! {
         {
-            use html_context::*;
+            #errors
 
             #builder
         }
     }
+}
+
+struct HtmlRoot {
+    context: Option<HtmlContext>,
+    children: Vec<HtmlElement>,
+}
+
+#[allow(dead_code)]
+struct HtmlContext {
+    in_token: syn::Token![in],
+    path: syn::Path,
+    options: Vec<HtmlContextOption>,
+    fat_arrow_token: syn::Token![=>],
+}
+
+/// An option given after the context: `in path, fallible =>`.
+#[allow(dead_code)]
+struct HtmlContextOption {
+    comma_token: syn::Token![,],
+    name: syn::Ident,
+}
+
+#[allow(dead_code)]
+struct HtmlMacroDefinition {
+    vis: syn::Visibility,
+    name: syn::Ident,
+    fat_arrow_token: syn::Token![=>],
+    context: syn::Path,
+    options: Vec<HtmlContextOption>,
+}
+
+/// The options of the code generation.
+struct Options {
+    /// The module where the elements, `Text`, `Fragment` and `DynamicElement` are looked up.
+    context: syn::Path,
+    /// The steps of the builders and `finish` return a `Result` that is propagated with `?`.
+    fallible: bool,
+}
+
+/// Define a `html!` macro for a framework with a fixed context module.
+///
+/// ```ignore
+/// yo_html::define_html_macro!(pub my_html => crate::html_context);
+/// ```
+///
+/// A public macro must be defined at the root of the crate, where the hidden re-export of `html!`
+/// it calls can be reached through `$crate`. The paths starting with `crate` are resolved in the
+/// crate defining the macro.
+#[proc_macro]
+pub fn define_html_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let definition = syn::parse_macro_input!(input as HtmlMacroDefinition);
+    let name = &definition.name;
+    let options = definition
+        .options
+        .iter()
+        .map(|x| &x.name)
+        .collect::<Vec<_>>();
+
+    // NOTE: `crate` must be replaced by `$crate` for the macro to work in other crates.
+    let mut context = proc_macro2::TokenStream::new();
+    for pair in definition.context.segments.pairs() {
+        let segment = pair.value();
+        if segment.ident == "crate" && context.is_empty() {
+            context.extend(quote::quote! { $crate });
+        } else {
+            let colon = &definition.context.leading_colon;
+            if context.is_empty() {
+                context.extend(quote::quote! { #colon });
+            }
+            context.extend(quote::quote! { #segment });
+        }
+        if let Some(punct) = pair.punct() {
+            context.extend(quote::quote! { #punct });
+        }
+    }
+
+    match &definition.vis {
+        syn::Visibility::Public(_) => {
+            // NOTE: the users of the framework may not depend on yo-html so `html!` is re-exported
+            //       where `$crate` can reach it: at the root. The path of any other module has a
+            //       `::` after the name of the crate.
+            let html = quote::format_ident!("__yo_html_{}", name);
+            let root_check = quote::quote_spanned! {name.span()=>
+                const _: () = {
+                    let path = ::core::module_path!().as_bytes();
+                    let mut i = 1;
+                    while i < path.len() {
+                        if path[i - 1] == b':' && path[i] == b':' {
+                            ::core::panic!("a `pub` html macro must be defined at the crate root");
+                        }
+                        i += 1;
+                    }
+                };
+            };
+            quote::quote! {
+                #[doc(hidden)]
+                pub use ::yo_html::html as #html;
+                #root_check
+
+                #[macro_export]
+                macro_rules! #name {
+                    ($($tt:tt)*) => {
+                        $crate::#html!(in #context #(, #options)* => $($tt)*)
+                    };
+                }
+            }
+        }
+        vis => {
+            let export = matches!(vis, syn::Visibility::Restricted(_))
+                .then(|| quote::quote! { #vis use #name; });
+            quote::quote! {
+                macro_rules! #name {
+                    ($($tt:tt)*) => {
+                        ::yo_html::html!(in #context #(, #options)* => $($tt)*)
+                    };
+                }
+                #export
+            }
+        }
+    }
     .into()
 }
 
+/// Derive the builder of a component from its props.
+///
+/// See the [crate documentation](crate#components) for the attributes.
+#[proc_macro_derive(HtmlBuilder, attributes(html))]
+pub fn derive_html_builder(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    derive::derive_html_builder(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Turn a function into a component that can be used as a tag.
+///
+/// See the [crate documentation](crate#components) for the options.
+#[proc_macro_attribute]
+pub fn component(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut options = component::ComponentOptions::default();
+    let parser = syn::meta::parser(|meta| options.parse(meta));
+    syn::parse_macro_input!(args with parser);
+    let item = syn::parse_macro_input!(input as syn::ItemFn);
+    component::component(options, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 enum HtmlElement {
     Tagged(HtmlElementTag),
     Fragmented(HtmlElementFragment),
     Block(syn::Block),
     Format(HtmlElementFormat),
+    Lit(syn::Lit),
+    #[cfg(feature = "jsx-text")]
+    Text(HtmlText),
+    If(HtmlElementIf),
+    For(HtmlElementFor),
+    Match(HtmlElementMatch),
+    Let(Box<syn::Local>),
+    Invalid(syn::Error),
 }
 
 #[allow(dead_code)]
@@ -63,6 +394,7 @@ struct HtmlElementTag {
     opening_tag: HtmlOpeningTag,
     children: Vec<HtmlElement>,
     closing_tag: Option<HtmlClosingTag>,
+    errors: Vec<syn::Error>,
 }
 
 #[allow(dead_code)]
@@ -88,24 +420,31 @@ struct HtmlClosingFragment {
 #[allow(dead_code)]
 struct HtmlOpeningTag {
     opening_bracket_token: syn::Token![<],
-    tag: syn::Ident,
-    generics: syn::Generics,
+    tag: HtmlTagName,
     attributes: Vec<HtmlAttribute>,
     self_closing_slash_token: Option<syn::Token![/]>,
     closing_bracket_token: syn::Token![>],
+    errors: Vec<syn::Error>,
 }
 
 #[allow(dead_code)]
 struct HtmlClosingTag {
     opening_bracket_token: syn::Token![<],
     closing_slash_token: syn::Token![/],
-    tag: syn::Ident,
+    tag: Option<HtmlTagName>,
     closing_bracket_token: syn::Token![>],
 }
 
+enum HtmlTagName {
+    Path(syn::Path),
+    Name(HtmlName),
+    Block(syn::Block),
+}
+
 #[allow(dead_code)]
 struct HtmlAttribute {
     name: HtmlAttributeName,
+    question_token: Option<syn::Token![?]>,
     eq_token: Option<syn::Token![=]>,
     value: Option<HtmlAttributeValue>,
 }
@@ -113,11 +452,24 @@ struct HtmlAttribute {
 #[allow(dead_code)]
 enum HtmlAttributeName {
     Block(syn::Block),
-    Ident(syn::Ident),
+    Name(HtmlName),
     Shorthand {
         brace_token: syn::token::Brace,
         ident: syn::Ident,
     },
+    Spread {
+        dot2_token: syn::Token![..],
+        expr: Box<syn::Expr>,
+    },
+}
+
+struct HtmlName {
+    segments: syn::punctuated::Punctuated<syn::Ident, HtmlNameSeparator>,
+}
+
+enum HtmlNameSeparator {
+    Dash(syn::Token![-]),
+    Colon(syn::Token![:]),
 }
 
 #[allow(dead_code)]
@@ -125,6 +477,57 @@ enum HtmlAttributeValue {
     Block(syn::Block),
     ExprArray(syn::ExprArray),
     Lit(syn::Lit),
+    Format(HtmlElementFormat),
+}
+
+#[allow(dead_code)]
+struct HtmlElementIf {
+    if_token: syn::Token![if],
+    cond: Box<syn::Expr>,
+    then_branch: HtmlBranch,
+    else_branch: Option<(syn::Token![else], HtmlElementElse)>,
+}
+
+enum HtmlElementElse {
+    If(Box<HtmlElementIf>),
+    Branch(HtmlBranch),
+}
+
+#[allow(dead_code)]
+struct HtmlBranch {
+    brace_token: syn::token::Brace,
+    children: Vec<HtmlElement>,
+}
+
+#[allow(dead_code)]
+struct HtmlElementFor {
+    for_token: syn::Token![for],
+    pat: Box<syn::Pat>,
+    in_token: syn::Token![in],
+    expr: Box<syn::Expr>,
+    body: HtmlBranch,
+}
+
+#[allow(dead_code)]
+struct HtmlElementMatch {
+    match_token: syn::Token![match],
+    expr: Box<syn::Expr>,
+    brace_token: syn::token::Brace,
+    arms: Vec<HtmlMatchArm>,
+}
+
+#[allow(dead_code)]
+struct HtmlMatchArm {
+    pat: syn::Pat,
+    guard: Option<(syn::Token![if], Box<syn::Expr>)>,
+    fat_arrow_token: syn::Token![=>],
+    body: Box<HtmlElement>,
+    comma: Option<syn::Token![,]>,
+}
+
+#[cfg(feature = "jsx-text")]
+struct HtmlText {
+    text: syn::LitStr,
 }
 
 #[allow(dead_code)]
@@ -1,6 +1,276 @@
 use super::*;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::parse::discouraged::Speculative;
 use syn::spanned::Spanned;
 
+impl syn::parse::Parse for HtmlRoot {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let context: Option<HtmlContext> = input
+            .peek(syn::Token![in])
+            .then(|| input.parse())
+            .transpose()?;
+        let mut children = Vec::new();
+        let mut prev = context.as_ref().map(|x| x.fat_arrow_token.spans[1]);
+        while !input.is_empty() {
+            children.push(parse_child(input, &mut prev));
+        }
+        Ok(Self { context, children })
+    }
+}
+
+impl syn::parse::Parse for HtmlContext {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            in_token: input.parse()?,
+            path: input.parse()?,
+            options: HtmlContextOption::parse_many(input)?,
+            fat_arrow_token: input.parse()?,
+        })
+    }
+}
+
+impl syn::parse::Parse for HtmlContextOption {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let comma_token = input.parse()?;
+        let name: syn::Ident = input.parse()?;
+        if name != "fallible" {
+            return Err(syn::Error::new_spanned(
+                name,
+                "unknown option, expected `fallible`",
+            ));
+        }
+        Ok(Self { comma_token, name })
+    }
+}
+
+impl HtmlContextOption {
+    fn parse_many(input: syn::parse::ParseStream) -> syn::Result<Vec<Self>> {
+        let mut options = Vec::new();
+        while input.peek(syn::Token![,]) {
+            options.push(input.parse()?);
+        }
+        Ok(options)
+    }
+}
+
+impl syn::parse::Parse for HtmlMacroDefinition {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            vis: input.parse()?,
+            name: input.parse()?,
+            fat_arrow_token: input.parse()?,
+            context: input.parse()?,
+            options: HtmlContextOption::parse_many(input)?,
+        })
+    }
+}
+
+impl HtmlRoot {
+    /// All the errors found while parsing, in the order of the source.
+    pub(crate) fn errors(&self) -> Vec<syn::Error> {
+        let mut errors = Vec::new();
+        for child in &self.children {
+            child.collect_errors(&mut errors);
+        }
+        errors
+    }
+}
+
+impl HtmlElement {
+    fn collect_errors(&self, errors: &mut Vec<syn::Error>) {
+        match self {
+            Self::Tagged(element) => {
+                errors.extend(element.opening_tag.errors.iter().cloned());
+                for child in &element.children {
+                    child.collect_errors(errors);
+                }
+                errors.extend(element.errors.iter().cloned());
+            }
+            Self::Fragmented(fragment) => {
+                for child in &fragment.children {
+                    child.collect_errors(errors);
+                }
+            }
+            Self::If(element) => element.collect_errors(errors),
+            Self::For(element) => element.body.collect_errors(errors),
+            Self::Match(element) => {
+                for arm in &element.arms {
+                    arm.body.collect_errors(errors);
+                }
+            }
+            Self::Block(_) | Self::Format(_) | Self::Lit(_) | Self::Let(_) => {}
+            #[cfg(feature = "jsx-text")]
+            Self::Text(_) => {}
+            Self::Invalid(err) => errors.push(err.clone()),
+        }
+    }
+}
+
+impl HtmlElementIf {
+    fn collect_errors(&self, errors: &mut Vec<syn::Error>) {
+        self.then_branch.collect_errors(errors);
+        match &self.else_branch {
+            Some((_, HtmlElementElse::If(element))) => element.collect_errors(errors),
+            Some((_, HtmlElementElse::Branch(branch))) => branch.collect_errors(errors),
+            None => {}
+        }
+    }
+}
+
+impl HtmlBranch {
+    fn collect_errors(&self, errors: &mut Vec<syn::Error>) {
+        for child in &self.children {
+            child.collect_errors(errors);
+        }
+    }
+}
+
+/// Parse the children until the end of the input or a closing tag. `prev` is the span of the token
+/// preceding the children.
+fn parse_children(
+    input: syn::parse::ParseStream,
+    mut prev: Option<proc_macro2::Span>,
+) -> Vec<HtmlElement> {
+    let mut children = Vec::new();
+    while !((input.peek(syn::Token![<]) && input.peek2(syn::Token![/])) || input.is_empty()) {
+        children.push(parse_child(input, &mut prev));
+    }
+    children
+}
+
+/// Parse an element. When it is malformed, the error is kept and the tokens are skipped until the
+/// next `<`, `{`, `(` or literal so the parsing can continue with the next element.
+///
+/// `prev` is the span of the last token before the element, it is updated once the element is
+/// parsed.
+fn parse_child(
+    input: syn::parse::ParseStream,
+    prev: &mut Option<proc_macro2::Span>,
+) -> HtmlElement {
+    let begin = input.cursor();
+    let fork = input.fork();
+    let element = match parse_element(&fork, *prev) {
+        Ok(element) => {
+            input.advance_to(&fork);
+            element
+        }
+        Err(err) => {
+            skip_until(input, |input| {
+                input.peek(syn::Token![<])
+                    || input.peek(syn::token::Brace)
+                    || input.peek(syn::token::Paren)
+                    || input.peek(syn::Lit)
+            });
+            HtmlElement::Invalid(err)
+        }
+    };
+    let mut cursor = begin;
+    while cursor < input.cursor() {
+        let Some((token, next)) = cursor.token_tree() else {
+            break;
+        };
+        *prev = Some(token.span());
+        cursor = next;
+    }
+    element
+}
+
+#[cfg(not(feature = "jsx-text"))]
+fn parse_element(
+    input: syn::parse::ParseStream,
+    _prev: Option<proc_macro2::Span>,
+) -> syn::Result<HtmlElement> {
+    input.parse()
+}
+
+#[cfg(feature = "jsx-text")]
+fn parse_element(
+    input: syn::parse::ParseStream,
+    prev: Option<proc_macro2::Span>,
+) -> syn::Result<HtmlElement> {
+    if HtmlText::peek(input) {
+        Ok(HtmlElement::Text(HtmlText::parse(input, prev)?))
+    } else {
+        input.parse()
+    }
+}
+
+#[cfg(feature = "jsx-text")]
+impl HtmlText {
+    /// Text starts with any token that can't start another kind of element.
+    fn peek(input: syn::parse::ParseStream) -> bool {
+        !(input.peek(syn::Token![<])
+            || input.peek(syn::token::Brace)
+            || input.peek(syn::token::Paren)
+            || input.peek(syn::Lit)
+            || input.peek(syn::Token![if])
+            || input.peek(syn::Token![for])
+            || input.peek(syn::Token![match])
+            || input.peek(syn::Token![let]))
+    }
+
+    /// Rebuild the text up to the next tag, block, format shorthand or literal from the source
+    /// code. The whitespace is collapsed like in JSX: it becomes a single space unless it is around
+    /// the text and contains a line break, then it is removed.
+    fn parse(input: syn::parse::ParseStream, prev: Option<proc_macro2::Span>) -> syn::Result<Self> {
+        let first = input.span();
+        let mut text = String::new();
+        if prev.is_some_and(|prev| whitespace(prev, first) == Some(false)) {
+            text.push(' ');
+        }
+        let mut last = None;
+        while !(input.peek(syn::Token![<])
+            || input.peek(syn::token::Brace)
+            || input.peek(syn::token::Paren)
+            || input.peek(syn::Lit)
+            || input.is_empty())
+        {
+            let token: proc_macro2::TokenTree = input.parse()?;
+            let span = token.span();
+            if last.is_some_and(|last| whitespace(last, span).is_some()) {
+                text.push(' ');
+            }
+            text.push_str(&span.source_text().unwrap_or_else(|| token.to_string()));
+            last = Some(span);
+        }
+        if let Some(last) = last.filter(|_| !input.is_empty()) {
+            if whitespace(last, input.span()) == Some(false) {
+                text.push(' ');
+            }
+        }
+        Ok(Self {
+            text: syn::LitStr::new(&text, first),
+        })
+    }
+}
+
+/// Check the whitespace between two tokens: `None` when there is no whitespace, `Some(true)` when
+/// it contains a line break.
+///
+/// NOTE: the positions of the spans are only available since Rust 1.88, hence the feature.
+#[cfg(feature = "jsx-text")]
+#[allow(clippy::incompatible_msrv)]
+fn whitespace(before: proc_macro2::Span, after: proc_macro2::Span) -> Option<bool> {
+    let end = before.unwrap().end();
+    let start = after.unwrap().start();
+    if end.line() != start.line() {
+        Some(true)
+    } else if end.column() != start.column() {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Skip at least one token, then all the tokens until the boundary is found.
+fn skip_until(input: syn::parse::ParseStream, boundary: impl Fn(syn::parse::ParseStream) -> bool) {
+    let _ = input.parse::<proc_macro2::TokenTree>();
+    while !(input.is_empty() || boundary(input)) {
+        let _ = input.parse::<proc_macro2::TokenTree>();
+    }
+}
+
 impl syn::parse::Parse for HtmlElement {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         assert!(
@@ -15,6 +285,28 @@ impl syn::parse::Parse for HtmlElement {
             Ok(Self::Block(input.parse()?))
         } else if input.peek(syn::token::Paren) {
             Ok(Self::Format(input.parse()?))
+        } else if input.peek(syn::Lit) {
+            match input.parse()? {
+                lit @ (syn::Lit::Str(_)
+                | syn::Lit::Char(_)
+                | syn::Lit::Int(_)
+                | syn::Lit::Float(_)) => Ok(Self::Lit(lit)),
+                lit => Err(syn::parse::Error::new_spanned(
+                    lit,
+                    "expected string, character or number literal",
+                )),
+            }
+        } else if input.peek(syn::Token![if]) {
+            Ok(Self::If(input.parse()?))
+        } else if input.peek(syn::Token![for]) {
+            Ok(Self::For(input.parse()?))
+        } else if input.peek(syn::Token![match]) {
+            Ok(Self::Match(input.parse()?))
+        } else if input.peek(syn::Token![let]) {
+            match input.parse()? {
+                syn::Stmt::Local(local) => Ok(Self::Let(Box::new(local))),
+                stmt => Err(syn::parse::Error::new_spanned(stmt, "expected let binding")),
+            }
         } else {
             Err(input.error("could not parse element"))
         }
@@ -26,28 +318,56 @@ impl syn::parse::Parse for HtmlElementTag {
         let opening_tag: HtmlOpeningTag = input.parse()?;
         let span = opening_tag.tag.span();
         let self_closing = opening_tag.self_closing_slash_token.is_some();
+        let children = if self_closing {
+            Vec::new()
+        } else {
+            parse_children(input, Some(opening_tag.closing_bracket_token.span))
+        };
+        let closing_tag = (!self_closing)
+            .then(|| {
+                input
+                    .parse::<HtmlClosingTag>()
+                    .map_err(|_| syn::parse::Error::new(span, "could not find matching close tag"))
+            })
+            .transpose()?;
+        let mut errors = Vec::new();
+        if let Some(closing_tag) = &closing_tag {
+            let matches = match &closing_tag.tag {
+                Some(tag) => tag.matches(&opening_tag.tag),
+                None => matches!(opening_tag.tag, HtmlTagName::Block(_)),
+            };
+            if !matches {
+                let expected = opening_tag.tag.to_string();
+                let found = closing_tag
+                    .tag
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                let message = if is_near_miss(&found, &expected) {
+                    format!("mismatched closing tag `</{found}>`, did you mean `</{expected}>`?")
+                } else {
+                    format!(
+                        "mismatched closing tag, expected `</{expected}>` but found `</{found}>`"
+                    )
+                };
+                let mut err = match &closing_tag.tag {
+                    Some(tag) => syn::parse::Error::new_spanned(tag, message),
+                    None => {
+                        syn::parse::Error::new_spanned(closing_tag.closing_slash_token, message)
+                    }
+                };
+                err.combine(syn::parse::Error::new_spanned(
+                    &opening_tag.tag,
+                    format!("unclosed tag `<{expected}>`"),
+                ));
+                errors.push(err);
+            }
+        }
         Ok(Self {
             opening_tag,
-            children: {
-                let mut children = Vec::new();
-                if !self_closing {
-                    while !((input.peek(syn::Token![<]) && input.peek2(syn::Token![/]))
-                        || input.is_empty())
-                    {
-                        children.push(input.parse()?);
-                    }
-                }
-                children
-            },
-            closing_tag: {
-                (!self_closing)
-                    .then(|| {
-                        input.parse::<HtmlClosingTag>().map_err(|_| {
-                            syn::parse::Error::new(span, "could not find matching close tag")
-                        })
-                    })
-                    .transpose()?
-            },
+            children,
+            closing_tag,
+            errors,
         })
     }
 }
@@ -55,17 +375,11 @@ impl syn::parse::Parse for HtmlElementTag {
 impl syn::parse::Parse for HtmlElementFragment {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let span = input.span();
+        let opening_fragment: HtmlOpeningFragment = input.parse()?;
+        let prev = opening_fragment.closing_bracket_token.span;
         Ok(Self {
-            opening_fragment: input.parse()?,
-            children: {
-                let mut children = Vec::new();
-                while !((input.peek(syn::Token![<]) && input.peek2(syn::Token![/]))
-                    || input.is_empty())
-                {
-                    children.push(input.parse()?);
-                }
-                children
-            },
+            opening_fragment,
+            children: parse_children(input, Some(prev)),
             closing_fragment: input.parse().map_err(|_| {
                 syn::parse::Error::new(span, "could not find matching close fragment")
             })?,
@@ -94,14 +408,46 @@ impl syn::parse::Parse for HtmlClosingFragment {
 
 impl syn::parse::Parse for HtmlOpeningTag {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut errors = Vec::new();
         Ok(Self {
             opening_bracket_token: input.parse()?,
             tag: input.parse()?,
-            generics: input.parse()?,
             attributes: {
                 let mut attrs = Vec::new();
-                while !(input.peek(syn::Token![>]) || input.peek(syn::Token![/])) {
-                    attrs.push(input.parse()?);
+                while !(input.peek(syn::Token![>])
+                    || input.peek(syn::Token![/])
+                    || input.is_empty())
+                {
+                    // NOTE: a malformed attribute is skipped up to the start of the next one.
+                    let fork = input.fork();
+                    match fork.parse() {
+                        Ok(attr) => {
+                            input.advance_to(&fork);
+                            attrs.push(attr);
+                        }
+                        Err(err) => {
+                            errors.push(err);
+                            // NOTE: the expression of a spread can contain identifiers.
+                            if input.parse::<Option<syn::Token![..]>>()?.is_some() {
+                                while !(input.is_empty()
+                                    || input.peek(syn::Token![>])
+                                    || input.peek(syn::Token![/])
+                                    || input.peek(syn::token::Brace)
+                                    || input.peek(syn::Token![..]))
+                                {
+                                    input.parse::<proc_macro2::TokenTree>()?;
+                                }
+                                continue;
+                            }
+                            skip_until(input, |input| {
+                                input.peek(syn::Token![>])
+                                    || input.peek(syn::Token![/])
+                                    || input.peek(syn::Ident::peek_any)
+                                    || input.peek(syn::token::Brace)
+                                    || input.peek(syn::Token![..])
+                            });
+                        }
+                    }
                 }
                 attrs
             },
@@ -112,6 +458,7 @@ impl syn::parse::Parse for HtmlOpeningTag {
                     .transpose()?
             },
             closing_bracket_token: input.parse()?,
+            errors,
         })
     }
 }
@@ -121,37 +468,146 @@ impl syn::parse::Parse for HtmlClosingTag {
         Ok(Self {
             opening_bracket_token: input.parse()?,
             closing_slash_token: input.parse()?,
-            tag: input.parse()?,
+            tag: {
+                (!input.peek(syn::Token![>]))
+                    .then(|| input.parse())
+                    .transpose()?
+            },
             closing_bracket_token: input.parse()?,
         })
     }
 }
 
+impl syn::parse::Parse for HtmlTagName {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Ident::peek_any)
+            && (input.peek2(syn::Token![-])
+                || (input.peek2(syn::Token![:]) && !input.peek2(syn::Token![::])))
+        {
+            Ok(Self::Name(input.parse()?))
+        } else if input.peek(syn::token::Brace) {
+            Ok(Self::Block(input.parse()?))
+        } else {
+            Ok(Self::Path(input.parse()?))
+        }
+    }
+}
+
+impl quote::ToTokens for HtmlTagName {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::Path(path) => path.to_tokens(tokens),
+            Self::Name(name) => name.to_tokens(tokens),
+            Self::Block(block) => block.to_tokens(tokens),
+        }
+    }
+}
+
+impl HtmlTagName {
+    /// Check that a closing tag matches this tag. The generic arguments of the closing tag are
+    /// optional and the blocks of dynamic tags must be identical.
+    fn matches(&self, opening: &HtmlTagName) -> bool {
+        match (self, opening) {
+            (Self::Path(closing), Self::Path(opening)) => {
+                closing.leading_colon.is_some() == opening.leading_colon.is_some()
+                    && closing.segments.len() == opening.segments.len()
+                    && closing.segments.iter().zip(opening.segments.iter()).all(
+                        |(closing, opening)| {
+                            closing.ident == opening.ident
+                                && (closing.arguments.is_empty()
+                                    || closing.arguments.to_token_stream().to_string()
+                                        == opening.arguments.to_token_stream().to_string())
+                        },
+                    )
+            }
+            (Self::Name(closing), Self::Name(opening)) => {
+                closing.to_string() == opening.to_string()
+            }
+            (Self::Block(closing), Self::Block(opening)) => {
+                closing.to_token_stream().to_string() == opening.to_token_stream().to_string()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A name is a near miss when only a few characters need to be changed to match the expected one.
+fn is_near_miss(name: &str, expected: &str) -> bool {
+    let distance = edit_distance(name, expected);
+    distance > 0 && distance <= (expected.chars().count() / 3).max(1)
+}
+
+/// The edit distance between two strings where a transposition of two adjacent characters counts
+/// as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
 impl syn::parse::Parse for HtmlAttribute {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let name: HtmlAttributeName = input.parse()?;
         let span = match &name {
             HtmlAttributeName::Block(block) => block.span(),
-            HtmlAttributeName::Ident(ident) => ident.span(),
+            HtmlAttributeName::Name(name) => name.span(),
             HtmlAttributeName::Shorthand { ident, .. } => ident.span(),
+            HtmlAttributeName::Spread { expr, .. } => expr.span(),
         };
         match name {
-            HtmlAttributeName::Shorthand { .. } => Ok(Self {
+            HtmlAttributeName::Shorthand { .. } | HtmlAttributeName::Spread { .. } => Ok(Self {
                 name,
+                question_token: None,
                 eq_token: None,
                 value: None,
             }),
+            HtmlAttributeName::Name(_)
+                if !(input.peek(syn::Token![=]) || input.peek(syn::Token![?])) =>
+            {
+                Ok(Self {
+                    name,
+                    question_token: None,
+                    eq_token: None,
+                    value: None,
+                })
+            }
             _ => {
+                let question_token: Option<syn::Token![?]> = input.parse()?;
+                let eq_token = input
+                    .parse()
+                    .map_err(|_| syn::parse::Error::new(span, "missing `=` token in attribute"))?;
+                let value = input
+                    .parse()
+                    .map_err(|_| syn::parse::Error::new(span, "missing value in attribute"))?;
+                if let (Some(_), HtmlAttributeValue::ExprArray(array)) = (&question_token, &value) {
+                    return Err(syn::parse::Error::new_spanned(
+                        array,
+                        "an optional attribute can't have many values",
+                    ));
+                }
                 Ok(Self {
                     name,
-                    eq_token: Some(input.parse().map_err(|_| {
-                        syn::parse::Error::new(span, "missing `=` token in attribute")
-                    })?),
-                    value: Some(
-                        input.parse().map_err(|_| {
-                            syn::parse::Error::new(span, "missing value in attribute")
-                        })?,
-                    ),
+                    question_token,
+                    eq_token: Some(eq_token),
+                    value: Some(value),
                 })
             }
         }
@@ -160,10 +616,25 @@ impl syn::parse::Parse for HtmlAttribute {
 
 impl syn::parse::Parse for HtmlAttributeName {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if input.peek(syn::Ident) {
-            Ok(Self::Ident(input.parse()?))
-        } else if input.peek(syn::token::Brace) && input.peek2(syn::Token![=]) {
+        if input.peek(syn::Ident::peek_any) {
+            Ok(Self::Name(input.parse()?))
+        } else if input.peek(syn::token::Brace)
+            && (input.peek2(syn::Token![=]) || input.peek2(syn::Token![?]))
+        {
             Ok(Self::Block(input.parse()?))
+        } else if input.peek(syn::Token![..]) {
+            Ok(Self::Spread {
+                dot2_token: input.parse()?,
+                expr: Box::new(if input.peek(syn::token::Brace) {
+                    syn::Expr::Block(input.parse()?)
+                } else if input.peek(syn::Ident) || input.peek(syn::Token![::]) {
+                    parse_spread_expr(input)?
+                } else {
+                    return Err(input.error(
+                        "expected a path, other expressions must be in a block: `..{expr}`",
+                    ));
+                }),
+            })
         } else if input.peek(syn::token::Brace) {
             let content;
             Ok(Self::Shorthand {
@@ -176,6 +647,96 @@ impl syn::parse::Parse for HtmlAttributeName {
     }
 }
 
+/// A path followed by field accesses, method calls, indexing and `?`: `props`, `props.attrs`,
+/// `props.attrs()` or `props[0]`. Other expressions must be in a block since they would take
+/// the next tokens.
+fn parse_spread_expr(input: syn::parse::ParseStream) -> syn::Result<syn::Expr> {
+    let mut expr = syn::Expr::Path(input.parse()?);
+    loop {
+        expr = if input.peek(syn::token::Bracket) {
+            let content;
+            syn::Expr::Index(syn::ExprIndex {
+                attrs: Vec::new(),
+                expr: Box::new(expr),
+                bracket_token: syn::bracketed!(content in input),
+                index: content.parse()?,
+            })
+        } else if input.peek(syn::Token![?]) {
+            syn::Expr::Try(syn::ExprTry {
+                attrs: Vec::new(),
+                expr: Box::new(expr),
+                question_token: input.parse()?,
+            })
+        } else if input.peek(syn::Token![.]) && !input.peek(syn::Token![..]) {
+            let dot_token = input.parse()?;
+            match input.parse()? {
+                syn::Member::Named(method) if input.peek(syn::token::Paren) => {
+                    let content;
+                    syn::Expr::MethodCall(syn::ExprMethodCall {
+                        attrs: Vec::new(),
+                        receiver: Box::new(expr),
+                        dot_token,
+                        method,
+                        turbofish: None,
+                        paren_token: syn::parenthesized!(content in input),
+                        args: content.parse_terminated(
+                            <syn::Expr as syn::parse::Parse>::parse,
+                            syn::Token![,],
+                        )?,
+                    })
+                }
+                member => syn::Expr::Field(syn::ExprField {
+                    attrs: Vec::new(),
+                    base: Box::new(expr),
+                    dot_token,
+                    member,
+                }),
+            }
+        } else {
+            return Ok(expr);
+        };
+    }
+}
+
+impl syn::parse::Parse for HtmlName {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut segments = syn::punctuated::Punctuated::new();
+        segments.push_value(syn::Ident::parse_any(input)?);
+        while input.peek(syn::Token![-])
+            || (input.peek(syn::Token![:]) && !input.peek(syn::Token![::]))
+        {
+            segments.push_punct(input.parse()?);
+            segments.push_value(syn::Ident::parse_any(input)?);
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl syn::parse::Parse for HtmlNameSeparator {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Token![-]) {
+            Ok(Self::Dash(input.parse()?))
+        } else {
+            Ok(Self::Colon(input.parse()?))
+        }
+    }
+}
+
+impl quote::ToTokens for HtmlNameSeparator {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::Dash(dash) => dash.to_tokens(tokens),
+            Self::Colon(colon) => colon.to_tokens(tokens),
+        }
+    }
+}
+
+impl quote::ToTokens for HtmlName {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.segments.to_tokens(tokens);
+    }
+}
+
 impl syn::parse::Parse for HtmlAttributeValue {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         if input.peek(syn::token::Brace) {
@@ -184,18 +745,118 @@ impl syn::parse::Parse for HtmlAttributeValue {
             Ok(Self::ExprArray(input.parse()?))
         } else if input.peek(syn::Lit) {
             Ok(Self::Lit(input.parse()?))
+        } else if input.peek(syn::token::Paren) {
+            Ok(Self::Format(input.parse()?))
         } else {
-            Err(input.error("expected block, array of expressions or literal"))
+            Err(input.error("expected block, array of expressions, literal or format arguments"))
         }
     }
 }
 
+impl syn::parse::Parse for HtmlElementIf {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            if_token: input.parse()?,
+            cond: Box::new(syn::Expr::parse_without_eager_brace(input)?),
+            then_branch: input.parse()?,
+            else_branch: {
+                input
+                    .peek(syn::Token![else])
+                    .then(|| -> syn::Result<_> {
+                        let else_token = input.parse()?;
+                        let else_branch = if input.peek(syn::Token![if]) {
+                            HtmlElementElse::If(Box::new(input.parse()?))
+                        } else {
+                            HtmlElementElse::Branch(input.parse()?)
+                        };
+                        Ok((else_token, else_branch))
+                    })
+                    .transpose()?
+            },
+        })
+    }
+}
+
+impl syn::parse::Parse for HtmlElementFor {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            for_token: input.parse()?,
+            pat: Box::new(syn::Pat::parse_multi_with_leading_vert(input)?),
+            in_token: input.parse()?,
+            expr: Box::new(syn::Expr::parse_without_eager_brace(input)?),
+            body: input.parse()?,
+        })
+    }
+}
+
+impl syn::parse::Parse for HtmlElementMatch {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            match_token: input.parse()?,
+            expr: Box::new(syn::Expr::parse_without_eager_brace(input)?),
+            brace_token: syn::braced!(content in input),
+            arms: {
+                let mut arms = Vec::new();
+                while !content.is_empty() {
+                    arms.push(content.parse()?);
+                }
+                arms
+            },
+        })
+    }
+}
+
+impl syn::parse::Parse for HtmlMatchArm {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            pat: syn::Pat::parse_multi_with_leading_vert(input)?,
+            guard: {
+                input
+                    .peek(syn::Token![if])
+                    .then(|| -> syn::Result<_> { Ok((input.parse()?, input.parse()?)) })
+                    .transpose()?
+            },
+            fat_arrow_token: input.parse()?,
+            body: {
+                // NOTE: the error is kept in the arm so the rest of the `match` is still checked.
+                if input.is_empty() || input.peek(syn::Token![,]) {
+                    Box::new(HtmlElement::Invalid(
+                        input.error("expected markup after `=>`"),
+                    ))
+                } else {
+                    input.parse()?
+                }
+            },
+            comma: input.parse()?,
+        })
+    }
+}
+
+impl syn::parse::Parse for HtmlBranch {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        Ok(Self {
+            brace_token: syn::braced!(content in input),
+            children: {
+                let mut children = Vec::new();
+                let mut prev = None;
+                while !content.is_empty() {
+                    children.push(parse_child(&content, &mut prev));
+                }
+                children
+            },
+        })
+    }
+}
+
 impl syn::parse::Parse for HtmlElementFormat {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let content;
         Ok(Self {
             paren_token: syn::parenthesized!(content in input),
-            args: content.parse_terminated(syn::Expr::parse, syn::Token![,])?,
+            args: content
+                .parse_terminated(<syn::Expr as syn::parse::Parse>::parse, syn::Token![,])?,
         })
     }
 }
@@ -5,6 +5,13 @@ fn tests_pass() {
     t.pass("tests/simple_web_framework/*-pass.rs");
 }
 
+#[cfg(feature = "jsx-text")]
+#[test]
+fn tests_jsx_text() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/simple_web_framework/jsx-text.rs");
+}
+
 #[allow(dead_code)]
 #[rustversion::attr(stable(1.75), test)]
 fn tests_fail() {
@@ -15,15 +15,45 @@ fn missing_value() {
     let _ = html! { <span {class}= /> };
 }
 
-fn missing_eq() {
-    let _ = html! { <span class /> };
-}
-
 fn duplicate_attributes() {
     let class = "46";
     let _ = html! { <span class="42" class="43" class=["44", "45"] {class} /> };
 }
 
+fn duplicate_hyphenated_attributes() {
+    let _ = html! { <span data-id="42" data-id="43" hx-get="/a" hx-get="/b" /> };
+}
+
+fn duplicate_keyword_attributes() {
+    let r#type = "text";
+    let _ = html! { <input type="text" r#type="text" {r#type} /> };
+}
+
+fn duplicate_flags() {
+    let _ = html! { <input disabled disabled data-dirty data-dirty /> };
+}
+
+fn duplicate_optional_attributes() {
+    let _ = html! { <span style="a" style?={Some("b")} /> };
+}
+
+fn optional_attribute_with_many_values() {
+    let _ = html! { <span class?=["a", "b"] /> };
+}
+
+fn invalid_spread() {
+    let _ = html! { <span ..42 /> };
+}
+
+fn spread_expression() {
+    let attrs = [("id", "a")];
+    let _ = html! { <span ..&attrs class="a" ..attrs /> };
+}
+
+fn invalid_hyphenated_attribute() {
+    let _ = html! { <span data- /> };
+}
+
 fn invalid_shorthand() {
     let _ = html! { <span {"class"} /> };
 }
@@ -11,6 +11,10 @@ fn main() {
     let name_expr = "name_expr";
     let var_class2 = "var_class2";
     let style = "style";
+    let r#type = "text";
+    let heading = "h1";
+    let attrs = [("title", "spread")];
+    let props = (attrs,);
 
     let _ = html! {
         <>
@@ -24,9 +28,135 @@ fn main() {
                 {some_string}
                 <MyComponent />
                 <MyComponent<u32> />
+                <basic_web_framework::MyComponent />
+                <crate::basic_web_framework::MyComponent<u32>></crate::basic_web_framework::MyComponent>
             </span>
+            if chosen_number > 40 {
+                <span>{"Big number"}</span>
+            } else if chosen_number > 20 {
+                {"Medium number"}
+            } else {
+                {"Small number"}
+                <br />
+            }
+            if chosen_number % 2 == 0 { <span>{"Even number"}</span> }
+            let half = chosen_number / 2;
+            <span>("Half: {}", half)</span>
+            if half > 10 {
+                let quarter = half / 2;
+                <span>("Quarter: {}", quarter)</span>
+            }
+            match chosen_number {
+                0 => <span>{"Zero"}</span>
+                n if n < 10 => ("Digit {}", n),
+                10 | 20 | 30 => if chosen_number == 10 { {"Ten"} },
+                _ => {"Many"}
+            }
+            <ul>
+                <li>{"First"}</li>
+                for i in 0..chosen_number {
+                    let label = format!("Item #{}", i);
+                    <li>{label}</li>
+                    if i % 2 == 0 { <br /> }
+                }
+                <li>{"Last"}</li>
+            </ul>
         </div>
+        for (i, word) in ["hello", "world"].iter().enumerate() {
+            <span>("{}: {}", i, word)</span>
+        }
+        <span data-id="42" aria-label={world} hx-get="/hello" data-classes=["a", "b"] />
+        <label for="name">{"Name"}</label>
+        <input type="text" data-for="name" />
+        <input {r#type} />
+        <input type="checkbox" checked disabled data-dirty />
+        <span class="a" ..attrs style="color: red" ..{vec![("id", "spread")]} />
+        <span ..props.0 ..props.0.iter().copied() class="b" />
+        <span style?={Some("color: red")} data-tooltip?={None::<&str>} {"style"}?={Some("")} />
+        <my-widget>
+            <svg><svg:a xlink:href="#hello">{"Hello"}</svg:a></svg>
+        </my-widget>
+        <span>"Hello " <b>"world"</b> '!' 42 1.5</span>
+        <span style=("color: {}", "red") data-hello=("Hello {}!", world) />
+        <{heading} class="title">{"Dynamic"}</{heading}>
+        <{if world.is_empty() { "h2" } else { "h3" }}>{"Dynamic"}</>
         <span />
         </>
     };
+    let div = "not an element";
+    let _ = html!(in ssr_context => <div>{div}</div> ("{}", 42) <{div} />);
+    let _ = html!(in crate::ssr_context =>);
+    let _ = html! {
+        <span>{"Implicit"}</span>
+        " fragment "
+        if world.is_empty() { <br /> }
+    };
+    let _ = html! {
+        in span_context =>
+        let text = "let";
+        <span>{text}</span>
+    };
+
+    let empty: [u32; 0] = [];
+    let hints = html!(in hint_context => <ul>for _ in empty { <li /> } <li /> <li /></ul>);
+    assert_eq!(hints, [2, 0]);
+    let hints = html!(in hint_context => <ul>for _ in [1, 2] { <li /> } <li /></ul>);
+    assert_eq!(hints, [3, 0, 0]);
+    let hints = html!(in hint_context => <ul><li />for _ in empty { <li /> }<li /></ul>);
+    assert_eq!(hints, [2, 1]);
+    let hints = html! {
+        in hint_context =>
+        <ul>
+            if true { for _ in [1, 2] { <li /> } }
+            <li />
+        </ul>
+    };
+    assert_eq!(hints, [3, 0, 0]);
+    let hints = html! {
+        in hint_context =>
+        <ul>
+            match 0 { 0 => for _ in empty { <li /> } _ => <li /> }
+            <li />
+        </ul>
+    };
+    assert_eq!(hints, [1]);
+}
+
+#[allow(non_camel_case_types)]
+mod ssr_context {
+    pub type div = super::basic_web_framework::VNode;
+    pub type Text = super::basic_web_framework::VNode;
+    pub type Fragment = super::basic_web_framework::VNode;
+    pub type DynamicElement = super::basic_web_framework::VNode;
+}
+
+#[allow(non_camel_case_types)]
+mod span_context {
+    pub type span = super::basic_web_framework::VNode;
+}
+
+/// A context whose builders return the reservation hints they received.
+#[allow(non_camel_case_types)]
+mod hint_context {
+    #[derive(Default)]
+    pub struct Hints(Vec<usize>);
+
+    impl Hints {
+        pub fn builder(_tag: &'static str) -> Self {
+            Self::default()
+        }
+
+        pub fn add_child<T>(&mut self, _child: T, additional: usize) -> &mut Self {
+            self.0.push(additional);
+            self
+        }
+
+        pub fn finish(&mut self) -> Vec<usize> {
+            std::mem::take(&mut self.0)
+        }
+    }
+
+    pub type ul = Hints;
+    pub type li = Hints;
+    pub type Fragment = Hints;
 }
@@ -13,6 +13,18 @@ pub enum VNode {
     Component(Rc<dyn Component>),
 }
 
+impl std::fmt::Display for VNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            VNode::Tagged { children, .. } | VNode::Fragment(children) => {
+                children.iter().try_for_each(|child| child.fmt(f))
+            }
+            VNode::Text(text) => text.fmt(f),
+            VNode::Component(_) => Ok(()),
+        }
+    }
+}
+
 impl From<String> for VNode {
     fn from(s: String) -> VNode {
         VNode::Text(s.into())
@@ -25,6 +37,24 @@ impl From<&'static str> for VNode {
     }
 }
 
+impl From<char> for VNode {
+    fn from(c: char) -> VNode {
+        VNode::Text(c.to_string().into())
+    }
+}
+
+impl From<i64> for VNode {
+    fn from(n: i64) -> VNode {
+        VNode::Text(n.to_string().into())
+    }
+}
+
+impl From<f64> for VNode {
+    fn from(n: f64) -> VNode {
+        VNode::Text(n.to_string().into())
+    }
+}
+
 impl From<std::fmt::Arguments<'_>> for VNode {
     fn from(args: std::fmt::Arguments) -> VNode {
         VNode::Text(args.into())
@@ -37,6 +67,12 @@ impl<const N: usize> From<[VNode; N]> for VNode {
     }
 }
 
+impl FromIterator<VNode> for VNode {
+    fn from_iter<I: IntoIterator<Item = VNode>>(iter: I) -> VNode {
+        VNode::Fragment(iter.into_iter().collect())
+    }
+}
+
 impl VNode {
     pub fn builder(_tag: &'static str) -> VNodeBuilder {
         VNodeBuilder::default()
@@ -50,6 +86,7 @@ pub struct VNodeBuilder {
     style: Option<IString>,
     numeric: Option<f64>,
     dyn_attrs: HashMap<IString, IString>,
+    flags: Vec<IString>,
     children: Vec<VNode>,
 }
 
@@ -79,6 +116,30 @@ impl VNodeBuilder {
         self
     }
 
+    pub fn set_attr_type(&mut self, r#type: impl Into<IString>) -> &mut Self {
+        self.add_attr("type", r#type, 1)
+    }
+
+    pub fn set_attr_for(&mut self, r#for: impl Into<IString>) -> &mut Self {
+        self.add_attr("for", r#for, 1)
+    }
+
+    pub fn set_attr_xlink_href(&mut self, href: impl Into<IString>) -> &mut Self {
+        self.add_attr("xlink:href", href, 1)
+    }
+
+    pub fn set_attr_hx_get(&mut self, url: impl Into<IString>) -> &mut Self {
+        self.add_attr("hx-get", url, 1)
+    }
+
+    pub fn set_flag_disabled(&mut self) -> &mut Self {
+        self.add_flag("disabled")
+    }
+
+    pub fn set_flag_checked(&mut self) -> &mut Self {
+        self.add_flag("checked")
+    }
+
     pub fn add_child(&mut self, element: impl Into<VNode>, additional: usize) -> &mut Self {
         //dbg!(additional);
         self.children.reserve_exact(additional);
@@ -100,6 +161,21 @@ impl VNodeBuilder {
         self
     }
 
+    pub fn spread_attrs<K: Into<IString>, V: Into<IString>>(
+        &mut self,
+        attrs: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut Self {
+        for (name, value) in attrs {
+            self.add_attr(name, value, 0);
+        }
+        self
+    }
+
+    pub fn add_flag(&mut self, name: impl Into<IString>) -> &mut Self {
+        self.flags.push(name.into());
+        self
+    }
+
     pub fn finish(&mut self) -> VNode {
         VNode::Tagged {
             tag: self.tag,
@@ -146,18 +222,249 @@ impl<T: 'static> From<MyComponentProps<T>> for VNode {
     }
 }
 
+pub struct FnComponent<P> {
+    props: P,
+    render: fn(&P) -> VNode,
+}
+
+impl<P> FnComponent<P> {
+    pub fn new(props: P, render: fn(&P) -> VNode) -> Self {
+        Self { props, render }
+    }
+
+    pub fn render(&self) -> VNode {
+        (self.render)(&self.props)
+    }
+}
+
+impl<P> Component for FnComponent<P> {}
+
+impl<P: 'static> From<FnComponent<P>> for VNode {
+    fn from(component: FnComponent<P>) -> VNode {
+        VNode::Component(Rc::new(component))
+    }
+}
+
+/// Rendered HTML built by owned builders.
+pub struct Markup(pub String);
+
+impl From<&'static str> for Markup {
+    fn from(s: &'static str) -> Markup {
+        Markup(s.to_string())
+    }
+}
+
+impl From<i64> for Markup {
+    fn from(n: i64) -> Markup {
+        Markup(n.to_string())
+    }
+}
+
+impl From<std::fmt::Arguments<'_>> for Markup {
+    fn from(args: std::fmt::Arguments) -> Markup {
+        Markup(args.to_string())
+    }
+}
+
+impl Markup {
+    pub fn builder(tag: &'static str) -> MarkupBuilder {
+        MarkupBuilder {
+            tag,
+            attrs: String::new(),
+            children: String::new(),
+        }
+    }
+}
+
+pub struct MarkupBuilder {
+    tag: &'static str,
+    attrs: String,
+    children: String,
+}
+
+impl MarkupBuilder {
+    pub fn set_attr_class(self, class: impl std::fmt::Display) -> Self {
+        self.add_attr("class", class, 1)
+    }
+
+    pub fn set_attr_title(self, title: impl std::fmt::Display) -> Self {
+        self.add_attr("title", title, 1)
+    }
+
+    pub fn set_flag_disabled(self) -> Self {
+        self.add_flag("disabled")
+    }
+
+    pub fn add_attr(
+        mut self,
+        name: impl std::fmt::Display,
+        value: impl std::fmt::Display,
+        _additional: usize,
+    ) -> Self {
+        self.attrs.push_str(&format!(" {name}=\"{value}\""));
+        self
+    }
+
+    pub fn add_flag(mut self, name: impl std::fmt::Display) -> Self {
+        self.attrs.push_str(&format!(" {name}"));
+        self
+    }
+
+    pub fn add_child(mut self, child: impl Into<Markup>, _additional: usize) -> Self {
+        self.children.push_str(&child.into().0);
+        self
+    }
+
+    pub fn finish(self) -> Markup {
+        if self.tag.is_empty() {
+            Markup(self.children)
+        } else {
+            let Self {
+                tag,
+                attrs,
+                children,
+            } = self;
+            Markup(format!("<{tag}{attrs}>{children}</{tag}>"))
+        }
+    }
+}
+
+/// An element rendered to `Markup` by a builder that validates its attributes and children.
+pub struct Checked;
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidHtml(pub String);
+
+impl std::fmt::Display for InvalidHtml {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidHtml {}
+
+impl Checked {
+    pub fn builder(tag: &'static str) -> CheckedBuilder {
+        CheckedBuilder {
+            tag,
+            attrs: String::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+pub struct CheckedBuilder {
+    tag: &'static str,
+    attrs: String,
+    children: Vec<Markup>,
+}
+
+impl CheckedBuilder {
+    pub fn set_attr_id(&mut self, id: &str) -> Result<&mut Self, InvalidHtml> {
+        if id.is_empty() || id.contains(char::is_whitespace) {
+            return Err(InvalidHtml(format!("invalid id {id:?}")));
+        }
+        self.add_attr("id", id, 1)
+    }
+
+    pub fn set_attr_title(
+        &mut self,
+        title: impl std::fmt::Display,
+    ) -> Result<&mut Self, InvalidHtml> {
+        self.add_attr("title", title, 1)
+    }
+
+    pub fn set_flag_disabled(&mut self) -> Result<&mut Self, InvalidHtml> {
+        self.add_flag("disabled")
+    }
+
+    pub fn add_attr(
+        &mut self,
+        name: &str,
+        value: impl std::fmt::Display,
+        _additional: usize,
+    ) -> Result<&mut Self, InvalidHtml> {
+        if self.attrs.contains(&format!(" {name}=")) {
+            return Err(InvalidHtml(format!("duplicate attribute {name:?}")));
+        }
+        self.attrs.push_str(&format!(" {name}=\"{value}\""));
+        Ok(self)
+    }
+
+    pub fn add_flag(&mut self, name: &str) -> Result<&mut Self, InvalidHtml> {
+        self.attrs.push_str(&format!(" {name}"));
+        Ok(self)
+    }
+
+    pub fn add_child(
+        &mut self,
+        child: impl Into<Markup>,
+        additional: usize,
+    ) -> Result<&mut Self, InvalidHtml> {
+        self.children.reserve(additional);
+        self.children.push(child.into());
+        Ok(self)
+    }
+
+    pub fn finish(&mut self) -> Result<Markup, InvalidHtml> {
+        let children = self.children.drain(..).map(|x| x.0).collect::<String>();
+        let (tag, attrs) = (self.tag, &self.attrs);
+        match tag {
+            "" => Ok(Markup(children)),
+            "input" if !children.is_empty() => {
+                Err(InvalidHtml(format!("<{tag}> can't have children")))
+            }
+            "input" => Ok(Markup(format!("<{tag}{attrs}>"))),
+            _ => Ok(Markup(format!("<{tag}{attrs}>{children}</{tag}>"))),
+        }
+    }
+}
+
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub mod fallible_context {
+    pub type div = super::Checked;
+    pub type span = super::Checked;
+    pub type input = super::Checked;
+    pub type DynamicElement = super::Checked;
+    pub type Text = super::Markup;
+    pub type Fragment = super::Checked;
+}
+
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub mod owned_context {
+    pub const OWNED_BUILDERS: bool = true;
+
+    pub type div = super::Markup;
+    pub type span = super::Markup;
+    pub type p = super::Markup;
+    pub type DynamicElement = super::Markup;
+    pub type Text = super::Markup;
+    pub type Fragment = super::Markup;
+}
+
 #[doc(hidden)]
 #[allow(non_camel_case_types)]
 pub mod html_context {
     pub type div = super::VNode;
     pub type span = super::VNode;
+    pub type b = super::VNode;
     pub type br = super::VNode;
+    pub type ul = super::VNode;
+    pub type li = super::VNode;
+    pub type label = super::VNode;
+    pub type input = super::VNode;
+    pub type my_widget = super::VNode;
+    pub type svg = super::VNode;
+    pub type svg_a = super::VNode;
+    pub type DynamicElement = super::VNode;
     pub type Text = super::VNode;
     pub type Fragment = super::VNode;
-    pub use super::MyComponent;
 }
 
 pub mod prelude {
     pub use super::html_context;
+    pub use super::MyComponent;
     pub use yo_html::html;
 }
@@ -0,0 +1,35 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+
+fn mismatched_closing_tag() {
+    let _ = html! { <div></span> };
+}
+
+fn misspelled_closing_tag() {
+    let _ = html! { <div></dvi> };
+}
+
+fn unclosed_nested_tag() {
+    let _ = html! { <div><span></div> };
+}
+
+fn mismatched_custom_element() {
+    let _ = html! { <my-widget></my-wigdet> };
+}
+
+fn mismatched_generics() {
+    let _ = html! { <MyComponent<u32>></MyComponent<u64>> };
+}
+
+fn mismatched_path() {
+    let _ = html! { <basic_web_framework::MyComponent></MyComponent> };
+}
+
+fn mismatched_dynamic_tag() {
+    let heading = "h1";
+    let _ = html! { <{heading}></{header}> };
+    let _ = html! { <div></> };
+}
+
+fn main() {}
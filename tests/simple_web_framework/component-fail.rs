@@ -0,0 +1,38 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+use basic_web_framework::VNode;
+
+#[yo_html::component]
+fn Card(title: &'static str, subtitle: &'static str, children: VNode) -> VNode {
+    html! { <div>{title}{subtitle}{children}</div> }
+}
+
+fn missing_prop() {
+    let _ = html! {
+        <div>
+            <Card title="Hello" />
+        </div>
+    };
+}
+
+fn missing_props_with_children() {
+    let items = ["a", "b"];
+    let _ = html! {
+        <Card>
+            for item in items { {item} }
+        </Card>
+    };
+}
+
+#[yo_html::component(render = true)]
+fn UnknownOption() -> VNode {
+    html! { <div /> }
+}
+
+#[yo_html::component]
+fn Pattern((a, b): (u32, u32)) -> u32 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,65 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+use basic_web_framework::{FnComponent, VNode};
+use implicit_clone::unsync::IString;
+
+/// A card with a title.
+#[yo_html::component]
+pub fn Card(
+    #[html(into)] title: IString,
+    #[html(default = 1)] level: u8,
+    #[html(into)] subtitle: Option<IString>,
+    children: VNode,
+) -> VNode {
+    html! {
+        <div>
+            ("{}", title)
+            ("{}", level)
+            if let Some(subtitle) = subtitle { <span>("{}", subtitle)</span> }
+            {children}
+        </div>
+    }
+}
+
+#[yo_html::component]
+fn Sum<T: std::ops::Add<Output = T>>(a: T, b: T) -> T {
+    a + b
+}
+
+#[yo_html::component(wrap = FnComponent::new)]
+#[derive(Clone, PartialEq)]
+fn Badge(label: &'static str, #[html(default)] count: u32) -> VNode {
+    html! { <span>{label}("{}", count)</span> }
+}
+
+fn main() {
+    let items = ["a", "b"];
+    let card = html! {
+        <Card title="Hello" subtitle="world">
+            <b>"bold"</b>
+            for item in items { {item} }
+        </Card>
+    };
+    assert_eq!(card.to_string(), "Hello1worldboldab");
+
+    let card = html! { <Card level={2} title={IString::from("Title")} /> };
+    assert_eq!(card.to_string(), "Title2");
+
+    for (subtitle, expected) in [(Some("sub"), "Title1sub"), (None, "Title1")] {
+        let card = html! { <Card subtitle?={subtitle} title="Title" /> };
+        assert_eq!(card.to_string(), expected);
+    }
+
+    assert_eq!(html! { <Sum<i32> a={1} b={2} /> }, 3);
+
+    let badge = html! { <Badge label="new" /> };
+    assert!(matches!(badge, VNode::Component(_)));
+    let props = Badge {
+        label: "new",
+        count: 3,
+    };
+    assert!(props == props.clone());
+    let component = FnComponent::new(props, |x| Badge(x.label, x.count));
+    assert_eq!(component.render().to_string(), "new3");
+}
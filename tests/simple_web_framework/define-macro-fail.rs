@@ -0,0 +1,11 @@
+pub mod basic_web_framework;
+
+yo_html::define_html_macro!(pub my_html => crate::basic_web_framework::html_context);
+
+mod nested {
+    yo_html::define_html_macro!(pub nested_html => crate::basic_web_framework::html_context);
+}
+
+fn main() {
+    let _ = my_html! { <div><unknown /></div> };
+}
@@ -0,0 +1,15 @@
+pub mod basic_web_framework;
+
+yo_html::define_html_macro!(pub my_html => crate::basic_web_framework::html_context);
+yo_html::define_html_macro!(local_html => crate::basic_web_framework::html_context);
+
+mod nested {
+    yo_html::define_html_macro!(pub(crate) crate_html => crate::basic_web_framework::html_context);
+}
+
+fn main() {
+    let div = "not an element";
+    let _ = my_html! { <div>{div}</div> };
+    let _ = local_html! { <span /> "text" };
+    let _ = nested::crate_html! { <span /> <span /> };
+}
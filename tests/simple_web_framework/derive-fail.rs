@@ -0,0 +1,27 @@
+#[derive(yo_html::HtmlBuilder)]
+struct UnknownOption {
+    #[html(required)]
+    value: u32,
+}
+
+#[derive(yo_html::HtmlBuilder)]
+#[html(with = Box::new)]
+struct WithoutFinish {
+    value: u32,
+}
+
+#[derive(yo_html::HtmlBuilder)]
+struct InvalidChildren {
+    children: String,
+}
+
+#[derive(yo_html::HtmlBuilder)]
+struct DefaultVec {
+    #[html(default)]
+    class: Vec<String>,
+}
+
+#[derive(yo_html::HtmlBuilder)]
+struct Tuple(u32);
+
+fn main() {}
@@ -0,0 +1,86 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+use basic_web_framework::{Component, VNode};
+use implicit_clone::unsync::IString;
+use std::rc::Rc;
+
+#[derive(yo_html::HtmlBuilder)]
+#[html(finish = VNode, with = |props| VNode::Component(Rc::new(props)))]
+pub struct Card {
+    #[html(into)]
+    title: IString,
+    #[html(default = 1)]
+    level: u8,
+    #[html(into)]
+    subtitle: Option<IString>,
+    class: Vec<&'static str>,
+    children: Vec<VNode>,
+}
+
+impl Component for Card {}
+
+#[derive(yo_html::HtmlBuilder)]
+pub struct Props<T: Default> {
+    #[html(default)]
+    value: T,
+    #[html(default = 10)]
+    limit: Option<u32>,
+}
+
+#[derive(yo_html::HtmlBuilder)]
+#[html(typestate)]
+pub struct Dialog<'a, T = u32> {
+    title: &'a str,
+    id: T,
+    #[html(default)]
+    open: bool,
+    class: Vec<&'static str>,
+    children: Vec<VNode>,
+}
+
+fn main() {
+    let card = html! {
+        <Card title="Hello" class=["a", "b"]>
+            <div />
+            {"text"}
+        </Card>
+    };
+    assert!(matches!(card, VNode::Component(_)));
+
+    let mut builder = Card::builder("Card");
+    builder
+        .set_attr_title("Hello")
+        .set_attr_level(2)
+        .set_attr_subtitle("world")
+        .add_attr_class("a", 1);
+    builder.add_child("text", 1);
+    assert!(matches!(builder.finish(), VNode::Component(_)));
+
+    let props = html! { <Props<i32> /> };
+    assert_eq!(props.value, 0);
+    assert_eq!(props.limit, Some(10));
+
+    let props = html! { <Props<i32> value={3} limit={5} /> };
+    assert_eq!(props.value, 3);
+    assert_eq!(props.limit, Some(5));
+
+    let items = ["a", "b"];
+    let dialog = html! {
+        <Dialog<u32> id={1} title="Hello" class=["a", "b"] open={true}>
+            for item in items { {item} }
+            <div />
+        </Dialog>
+    };
+    assert_eq!(dialog.title, "Hello");
+    assert_eq!(dialog.id, 1);
+    assert!(dialog.open);
+    assert_eq!(dialog.class, ["a", "b"]);
+    assert_eq!(dialog.children.len(), 3);
+
+    let dialog = Dialog::<u32>::builder("Dialog")
+        .set_attr_title("Hello")
+        .set_attr_id(2)
+        .finish();
+    assert!(!dialog.open);
+}
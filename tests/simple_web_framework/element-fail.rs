@@ -18,4 +18,17 @@ fn invalid_token_in_element() {
     let _ = html! { <div /!> };
 }
 
+fn invalid_literal() {
+    let _ = html! { <span>true b"bytes"</span> };
+}
+
+fn empty_match_arm() {
+    let _ = html! { match 0 { 0 => } };
+    let _ = html! { match 0 { 0 =>, _ => <br /> } };
+}
+
+fn unknown_context_option() {
+    let _ = html! { in html_context, borrowed => <div /> };
+}
+
 fn main() {}
@@ -0,0 +1,14 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+
+#[allow(non_camel_case_types)]
+mod html_context {
+    pub type span = super::basic_web_framework::VNode;
+}
+
+fn many_root_elements_without_fragment() {
+    let _ = html! { <span /> <span /> };
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+
+fn main() {
+    let name = "world";
+    let count = 3;
+    let vnode = html! {
+        <div>
+            <span>Hello {name}!</span>
+            <span>
+                Text spanning
+                many   lines.
+            </span>
+            <span><b>Do not</b> panic, this is only text.</span>
+            "Literals" and {"blocks"} still work
+            <span>("format {}", 42)</span>
+            <span>Count: ("{}", count) items</span>
+            <span>"a" b "c"</span>
+            <span>"for" you, if any</span>
+        </div>
+    };
+    assert_eq!(
+        vnode.to_string(),
+        concat!(
+            "Hello world!Text spanning many lines.Do not panic, this is only text.",
+            "Literals and blocks still workformat 42Count: 3 itemsa b cfor you, if any",
+        ),
+    );
+}
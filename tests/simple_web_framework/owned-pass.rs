@@ -0,0 +1,56 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::owned_context;
+use basic_web_framework::Markup;
+
+yo_html::define_html_macro!(ssr => crate::basic_web_framework::owned_context);
+
+#[derive(yo_html::HtmlBuilder)]
+#[html(finish = Markup, with = render_card, typestate)]
+pub struct Card {
+    title: &'static str,
+    #[html(default)]
+    level: i64,
+    children: Vec<Markup>,
+}
+
+fn render_card(card: Card) -> Markup {
+    ssr! {
+        <div class="card">
+            <p>("{} ({})", card.title, card.level)</p>
+            for child in card.children { {child} }
+        </div>
+    }
+}
+
+fn main() {
+    let title = Some("hello");
+    let items = [1, 2];
+    let markup = yo_html::html! {
+        in owned_context =>
+        <div class="list" title?={title} disabled>
+            for item in items { <span>{item}</span> }
+            if items.is_empty() { <p>"empty"</p> }
+        </div>
+    };
+    assert_eq!(
+        markup.0,
+        r#"<div class="list" title="hello" disabled><span>1</span><span>2</span></div>"#,
+    );
+
+    let tag = "p";
+    let markup = ssr! {
+        <{tag}>"text"</{tag}>
+        <Card title="Card" level={2}>
+            for item in items { <span>{item}</span> }
+            "end"
+        </Card>
+    };
+    assert_eq!(
+        markup.0,
+        concat!(
+            "<p>text</p>",
+            r#"<div class="card"><p>Card (2)</p><span>1</span><span>2</span>end</div>"#,
+        ),
+    );
+}
@@ -0,0 +1,16 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+
+fn many_errors() {
+    let _ = html! {
+        <div class= style="color: red">
+            <span /!>
+            <ul></ol>
+            / "text"
+            <span class={undefined_value}>{"still checked"}</span>
+        </div>
+    };
+}
+
+fn main() {}
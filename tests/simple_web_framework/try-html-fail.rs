@@ -0,0 +1,18 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::fallible_context;
+use basic_web_framework::Markup;
+use yo_html::try_html;
+
+#[derive(Debug)]
+struct RenderError;
+
+fn error_not_converted(id: &str) -> Result<Markup, RenderError> {
+    Ok(try_html! { in fallible_context => <div {id}>"text"</div> })
+}
+
+fn not_a_result(id: &str) -> Markup {
+    try_html! { in fallible_context => <div {id} /> }
+}
+
+fn main() {}
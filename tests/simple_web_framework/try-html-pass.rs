@@ -0,0 +1,51 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::fallible_context;
+use basic_web_framework::{InvalidHtml, Markup};
+use yo_html::try_html;
+
+yo_html::define_html_macro!(checked => crate::basic_web_framework::fallible_context, fallible);
+
+fn render(id: &str, items: &[i64]) -> Result<Markup, InvalidHtml> {
+    let markup = try_html! {
+        in fallible_context =>
+        <div {id}>
+            for item in items { <span>{*item}</span> }
+            if items.is_empty() { <span>"empty"</span> }
+        </div>
+    };
+    Ok(markup)
+}
+
+fn render_input(value: &'static str) -> Result<Markup, Box<dyn std::error::Error>> {
+    let title = Some("title");
+    Ok(checked! {
+        <input data-value={value} title?={title} disabled>
+            if value.is_empty() { "empty" }
+        </input>
+    })
+}
+
+fn main() {
+    assert_eq!(
+        render("list", &[1, 2]).unwrap().0,
+        r#"<div id="list"><span>1</span><span>2</span></div>"#,
+    );
+    assert_eq!(
+        render("list", &[]).unwrap().0,
+        r#"<div id="list"><span>empty</span></div>"#,
+    );
+    assert_eq!(
+        render("two words", &[1]).err(),
+        Some(InvalidHtml("invalid id \"two words\"".to_string())),
+    );
+
+    assert_eq!(
+        render_input("a").unwrap().0,
+        r#"<input data-value="a" title="title" disabled>"#,
+    );
+    assert_eq!(
+        render_input("").err().map(|x| x.to_string()).as_deref(),
+        Some("<input> can't have children"),
+    );
+}
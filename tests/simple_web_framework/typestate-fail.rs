@@ -0,0 +1,23 @@
+pub mod basic_web_framework;
+
+use basic_web_framework::prelude::*;
+use basic_web_framework::VNode;
+
+#[derive(yo_html::HtmlBuilder)]
+#[html(typestate)]
+pub struct Dialog {
+    title: &'static str,
+    #[html(default)]
+    open: bool,
+    children: Vec<VNode>,
+}
+
+fn missing_attribute() {
+    let _ = html! {
+        <Dialog open={true}>
+            <span />
+        </Dialog>
+    };
+}
+
+fn main() {}